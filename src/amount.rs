@@ -0,0 +1,393 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use alloy::{
+    network::Network,
+    primitives::{Address, U256, U512},
+    providers::{PendingTransactionBuilder, Provider},
+};
+use bigdecimal::BigDecimal;
+
+use crate::{
+    error::InternalError, Error, FormatOptions, LazyTokenSigner, Rounding, Token, TokenId,
+    TokenStore,
+};
+
+/// An amount of a given token, in base units.
+///
+/// Arithmetic and comparisons are only defined between amounts of the same
+/// token, identified by its address.
+#[derive(Debug, Clone)]
+pub struct TokenAmount {
+    token: Token,
+    raw: U256,
+}
+
+impl TokenAmount {
+    /// Creates a new [`TokenAmount`] of `raw` base units of `token`.
+    pub const fn new(token: Token, raw: U256) -> Self {
+        Self { token, raw }
+    }
+
+    /// Creates a new [`TokenAmount`] from a decimal `value` of `token`, like
+    /// `1.5` for 1.5 tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is negative, has more decimals than the
+    /// token, or doesn't fit in a `U256`.
+    pub fn from_decimal(token: Token, value: &BigDecimal) -> Result<Self, Error> {
+        Self::from_decimal_rounded(token, value, Rounding::Exact)
+    }
+
+    /// Creates a new [`TokenAmount`] from a decimal `value` of `token`,
+    /// rounding the decimals in excess.
+    ///
+    /// See [`Token::to_base_units`].
+    pub fn from_decimal_rounded(
+        token: Token,
+        value: &BigDecimal,
+        rounding: Rounding,
+    ) -> Result<Self, Error> {
+        let raw = token.to_base_units(value, rounding)?;
+
+        Ok(Self::new(token, raw))
+    }
+
+    /// Parses an amount followed by a token symbol or address, like
+    /// `"1.5 USDC"`, looking the token up in `store`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token isn't in `store`, or the amount isn't
+    /// valid for the token, see [`TokenAmount::from_decimal`]. Errors are
+    /// reported for the given symbol or address, or for an empty symbol if
+    /// `s` has no token.
+    pub fn parse<'a, S>(s: &str, chain_id: u64, store: &'a S) -> Result<Self, Error>
+    where
+        S: TokenStore<'a>,
+    {
+        let (value, id) = s
+            .trim()
+            .split_once(char::is_whitespace)
+            .map(|(value, id)| (value, parse_token_id(id.trim())))
+            .ok_or_else(|| {
+                Error::new(
+                    TokenId::Symbol(String::new()),
+                    InternalError::InvalidAmount(s.to_string()),
+                )
+            })?;
+
+        let value = BigDecimal::from_str(value)
+            .map_err(|_| Error::new(id.clone(), InternalError::InvalidAmount(value.to_string())))?;
+
+        let token = store
+            .get(chain_id, id.clone())
+            .ok_or_else(|| Error::new(id.clone(), InternalError::NotInStore(id.to_string())))?;
+
+        Self::from_decimal(token.clone(), &value)
+    }
+
+    /// Returns the amount token.
+    pub const fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Returns the amount in base units.
+    pub const fn raw(&self) -> U256 {
+        self.raw
+    }
+
+    /// Returns the amount as a [`BigDecimal`].
+    pub fn to_decimal(&self) -> BigDecimal {
+        self.token.get_balance(self.raw)
+    }
+
+    /// Formats the amount with the given `options`.
+    ///
+    /// See [`Token::format_units`].
+    pub fn format(&self, options: &FormatOptions) -> String {
+        self.token.format_units(self.raw, options)
+    }
+
+    /// Adds `other` to this amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `other` is an amount of another token, or on
+    /// overflow.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, Error> {
+        self.ensure_same_token(&other.token)?;
+
+        let raw = self
+            .raw
+            .checked_add(other.raw)
+            .ok_or_else(|| self.error(InternalError::Overflow))?;
+
+        Ok(Self::new(self.token.clone(), raw))
+    }
+
+    /// Subtracts `other` from this amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `other` is an amount of another token, or on
+    /// underflow.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, Error> {
+        self.ensure_same_token(&other.token)?;
+
+        let raw = self
+            .raw
+            .checked_sub(other.raw)
+            .ok_or_else(|| self.error(InternalError::Underflow))?;
+
+        Ok(Self::new(self.token.clone(), raw))
+    }
+
+    /// Multiplies this amount by `numerator / denominator`, rounding down.
+    ///
+    /// The intermediate product doesn't overflow, so this can be used to
+    /// apply a percentage or a price ratio to any amount.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `denominator` is zero, or if the result doesn't
+    /// fit in a `U256`.
+    pub fn mul_ratio(&self, numerator: U256, denominator: U256) -> Result<Self, Error> {
+        if denominator.is_zero() {
+            return Err(self.error(InternalError::InvalidAmount("division by zero".to_string())));
+        }
+
+        let product: U512 = self.raw.widening_mul(numerator);
+        let raw = U256::checked_from_limbs_slice((product / U512::from(denominator)).as_limbs())
+            .ok_or_else(|| self.error(InternalError::Overflow))?;
+
+        Ok(Self::new(self.token.clone(), raw))
+    }
+
+    fn ensure_same_token(&self, token: &Token) -> Result<(), Error> {
+        if self.token.address == token.address {
+            Ok(())
+        } else {
+            Err(self.error(InternalError::TokenMismatch(token.address)))
+        }
+    }
+
+    fn error(&self, err: InternalError) -> Error {
+        Error::new(self.token.address.into(), err)
+    }
+}
+
+/// Parses a token address, or falls back to a symbol.
+fn parse_token_id(s: &str) -> TokenId {
+    s.parse::<Address>()
+        .map_or_else(|_| TokenId::Symbol(s.to_string()), TokenId::Address)
+}
+
+impl PartialEq for TokenAmount {
+    fn eq(&self, other: &Self) -> bool {
+        self.token.address == other.token.address && self.raw == other.raw
+    }
+}
+
+impl PartialOrd for TokenAmount {
+    /// Compares two amounts of the same token, returning `None` for amounts
+    /// of different tokens.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.token.address == other.token.address).then(|| self.raw.cmp(&other.raw))
+    }
+}
+
+impl Display for TokenAmount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format(&FormatOptions::new().with_symbol()))
+    }
+}
+
+impl<P, N> LazyTokenSigner<P, N>
+where
+    P: Provider<N> + Clone,
+    N: Network,
+{
+    /// Transfers `amount` to `to`.
+    ///
+    /// This is the [`TokenAmount`] counterpart of
+    /// [`LazyTokenSigner::transfer`], which takes base units as the
+    /// contract does, for amounts that are already raw like balances. The
+    /// amount token is checked first, so an amount of another token can't
+    /// be sent by mistake.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `amount` is an amount of another token, or if the
+    /// transaction fails to send.
+    pub async fn transfer_amount(
+        &self,
+        to: Address,
+        amount: &TokenAmount,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.ensure_token(amount)?;
+
+        self.transfer(to, amount.raw()).await
+    }
+
+    /// Approves `spender` to transfer up to `amount` on behalf of the
+    /// caller.
+    ///
+    /// This is the [`TokenAmount`] counterpart of
+    /// [`LazyTokenSigner::approve`], see [`LazyTokenSigner::transfer_amount`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `amount` is an amount of another token, or if the
+    /// transaction fails to send.
+    pub async fn approve_amount(
+        &self,
+        spender: Address,
+        amount: &TokenAmount,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.ensure_token(amount)?;
+
+        self.approve(spender, amount.raw()).await
+    }
+
+    fn ensure_token(&self, amount: &TokenAmount) -> Result<(), Error> {
+        if amount.token().address == *self.address() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                (*self.address()).into(),
+                InternalError::TokenMismatch(amount.token().address),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use alloy::primitives::{address, U256};
+    use bigdecimal::BigDecimal;
+
+    use super::TokenAmount;
+    use crate::{BasicTokenStore, InternalError, Rounding, Token, TokenId, TokenStore};
+
+    fn usdc() -> Token {
+        Token::new(
+            address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            "USDC".to_string(),
+            6,
+        )
+    }
+
+    fn dai() -> Token {
+        Token::new(
+            address!("6B175474E89094C44Da98b954EedeAC495271d0F"),
+            "DAI".to_string(),
+            18,
+        )
+    }
+
+    #[test]
+    fn test_from_decimal() {
+        let value = BigDecimal::from_str("1.5").unwrap();
+        let amount = TokenAmount::from_decimal(usdc(), &value).unwrap();
+
+        assert_eq!(amount.raw(), U256::from(1_500_000));
+        assert_eq!(amount.to_decimal(), value);
+        assert_eq!(amount.to_string(), "1.5 USDC");
+    }
+
+    #[test]
+    fn test_from_decimal_excess_precision() {
+        let value = BigDecimal::from_str("1.0000001").unwrap();
+        let err = TokenAmount::from_decimal(usdc(), &value).unwrap_err();
+
+        assert!(matches!(err.source, InternalError::ExcessPrecision(_)));
+
+        let amount = TokenAmount::from_decimal_rounded(usdc(), &value, Rounding::Up).unwrap();
+        assert_eq!(amount.raw(), U256::from(1_000_001));
+    }
+
+    #[test]
+    fn test_from_decimal_negative() {
+        let value = BigDecimal::from_str("-1").unwrap();
+
+        assert!(TokenAmount::from_decimal(usdc(), &value).is_err());
+    }
+
+    #[test]
+    fn test_from_decimal_overflow() {
+        let value = BigDecimal::from_str("1e80").unwrap();
+        let err = TokenAmount::from_decimal(dai(), &value).unwrap_err();
+
+        assert!(matches!(err.source, InternalError::Overflow));
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut store = BasicTokenStore::new();
+        store.insert(1, usdc());
+
+        let amount = TokenAmount::parse("1.5 USDC", 1, &store).unwrap();
+        assert_eq!(amount.raw(), U256::from(1_500_000));
+
+        let err = TokenAmount::parse("1.5 DAI", 1, &store).unwrap_err();
+        assert!(matches!(err.source, InternalError::NotInStore(_)));
+
+        let amount =
+            TokenAmount::parse("2 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", 1, &store).unwrap();
+        assert_eq!(amount.raw(), U256::from(2_000_000));
+
+        let err = TokenAmount::parse("1.5 DAI", 1, &store).unwrap_err();
+        assert_eq!(err.token, TokenId::Symbol("DAI".to_string()));
+
+        let err = TokenAmount::parse("1,5 USDC", 1, &store).unwrap_err();
+        assert!(matches!(err.source, InternalError::InvalidAmount(ref value) if value == "1,5"));
+        assert_eq!(err.token, TokenId::Symbol("USDC".to_string()));
+
+        let err = TokenAmount::parse("1.5", 1, &store).unwrap_err();
+        assert!(matches!(err.source, InternalError::InvalidAmount(_)));
+        assert_eq!(err.token, TokenId::Symbol(String::new()));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let one = TokenAmount::new(usdc(), U256::from(1_000_000));
+        let two = TokenAmount::new(usdc(), U256::from(2_000_000));
+
+        assert_eq!(one.checked_add(&one).unwrap(), two);
+        assert_eq!(two.checked_sub(&one).unwrap(), one);
+        assert!(matches!(
+            one.checked_sub(&two).unwrap_err().source,
+            InternalError::Underflow
+        ));
+        assert_eq!(two.mul_ratio(U256::from(1), U256::from(2)).unwrap(), one);
+        assert_eq!(
+            TokenAmount::new(usdc(), U256::MAX)
+                .mul_ratio(U256::from(2), U256::from(3))
+                .unwrap()
+                .raw(),
+            U256::MAX / U256::from(3) * U256::from(2)
+        );
+        assert!(one.mul_ratio(U256::from(1), U256::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_mixed_tokens() {
+        let usdc = TokenAmount::new(usdc(), U256::from(1));
+        let dai = TokenAmount::new(dai(), U256::from(1));
+
+        assert_ne!(usdc, dai);
+        assert_eq!(usdc.partial_cmp(&dai), None);
+        assert!(matches!(
+            usdc.checked_add(&dai).unwrap_err().source,
+            InternalError::TokenMismatch(_)
+        ));
+        assert!(usdc < TokenAmount::new(usdc.token().clone(), U256::from(2)));
+    }
+}
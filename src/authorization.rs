@@ -0,0 +1,357 @@
+use alloy::{
+    network::Network,
+    primitives::{Address, Signature, B256, U256},
+    providers::{PendingTransactionBuilder, Provider},
+    signers::Signer,
+    sol,
+    sol_types::SolStruct,
+};
+
+use crate::{Error, LazyToken, LazyTokenSigner};
+
+sol! {
+    #[sol(rpc)]
+    #[derive(Debug)]
+    #[allow(clippy::too_many_arguments)]
+    interface IERC3009 {
+        function transferWithAuthorization(address from, address to, uint256 value, uint256 validAfter, uint256 validBefore, bytes32 nonce, uint8 v, bytes32 r, bytes32 s) external;
+        function receiveWithAuthorization(address from, address to, uint256 value, uint256 validAfter, uint256 validBefore, bytes32 nonce, uint8 v, bytes32 r, bytes32 s) external;
+        function cancelAuthorization(address authorizer, bytes32 nonce, uint8 v, bytes32 r, bytes32 s) external;
+        function authorizationState(address authorizer, bytes32 nonce) external view returns (bool);
+    }
+
+    /// The [EIP-3009] `TransferWithAuthorization` typed data.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    #[derive(Debug, PartialEq, Eq)]
+    struct TransferWithAuthorization {
+        address from;
+        address to;
+        uint256 value;
+        uint256 validAfter;
+        uint256 validBefore;
+        bytes32 nonce;
+    }
+
+    /// The [EIP-3009] `ReceiveWithAuthorization` typed data.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    #[derive(Debug, PartialEq, Eq)]
+    struct ReceiveWithAuthorization {
+        address from;
+        address to;
+        uint256 value;
+        uint256 validAfter;
+        uint256 validBefore;
+        bytes32 nonce;
+    }
+
+    /// The [EIP-3009] `CancelAuthorization` typed data.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    #[derive(Debug, PartialEq, Eq)]
+    struct CancelAuthorization {
+        address authorizer;
+        bytes32 nonce;
+    }
+}
+
+/// A signed [EIP-3009] authorization, ready to be submitted or relayed.
+///
+/// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedAuthorization<T> {
+    /// The authorization.
+    pub authorization: T,
+    /// The authorization signature, from its authorizer.
+    pub signature: Signature,
+}
+
+impl<P, N> LazyToken<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Returns `true` if the [EIP-3009] `nonce` of `authorizer` has already
+    /// been used or canceled.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    pub async fn authorization_state(
+        &self,
+        authorizer: Address,
+        nonce: B256,
+    ) -> Result<bool, Error> {
+        IERC3009::new(*self.address(), self.provider())
+            .authorizationState(authorizer, nonce)
+            .call()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))
+    }
+}
+
+impl<P, N> LazyTokenSigner<P, N>
+where
+    P: Provider<N> + Clone,
+    N: Network,
+{
+    /// Returns `true` if the [EIP-3009] `nonce` of `authorizer` has already
+    /// been used or canceled.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    pub async fn authorization_state(
+        &self,
+        authorizer: Address,
+        nonce: B256,
+    ) -> Result<bool, Error> {
+        self.token.authorization_state(authorizer, nonce).await
+    }
+
+    /// Signs an [EIP-3009] authorization to transfer `value` tokens from
+    /// `signer` to `to`, between `valid_after` and `valid_before`.
+    ///
+    /// Authorizations are identified by a random `nonce` rather than a
+    /// sequential one, so many can be pending at once. The signed
+    /// authorization can be submitted by anyone with
+    /// [`LazyTokenSigner::transfer_with_authorization`].
+    ///
+    /// The domain is resolved with [`LazyToken::eip712_domain`].
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token domain can't be resolved, or if the
+    /// signer fails.
+    pub async fn sign_transfer_authorization<S: Signer>(
+        &self,
+        signer: &S,
+        to: Address,
+        value: U256,
+        valid_after: U256,
+        valid_before: U256,
+        nonce: B256,
+    ) -> Result<SignedAuthorization<TransferWithAuthorization>, Error> {
+        let authorization = TransferWithAuthorization {
+            from: signer.address(),
+            to,
+            value,
+            validAfter: valid_after,
+            validBefore: valid_before,
+            nonce,
+        };
+
+        self.sign_authorization(signer, authorization).await
+    }
+
+    /// Signs an [EIP-3009] authorization to transfer `value` tokens from
+    /// `signer` to `to`, that only `to` can submit, with
+    /// [`LazyTokenSigner::receive_with_authorization`].
+    ///
+    /// This prevents front-running the transfer when it's part of a
+    /// contract call, see
+    /// [`LazyTokenSigner::sign_transfer_authorization`].
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    pub async fn sign_receive_authorization<S: Signer>(
+        &self,
+        signer: &S,
+        to: Address,
+        value: U256,
+        valid_after: U256,
+        valid_before: U256,
+        nonce: B256,
+    ) -> Result<SignedAuthorization<ReceiveWithAuthorization>, Error> {
+        let authorization = ReceiveWithAuthorization {
+            from: signer.address(),
+            to,
+            value,
+            validAfter: valid_after,
+            validBefore: valid_before,
+            nonce,
+        };
+
+        self.sign_authorization(signer, authorization).await
+    }
+
+    /// Signs the cancellation of the pending [EIP-3009] authorization of
+    /// `signer` with `nonce`.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    pub async fn sign_cancel_authorization<S: Signer>(
+        &self,
+        signer: &S,
+        nonce: B256,
+    ) -> Result<SignedAuthorization<CancelAuthorization>, Error> {
+        let authorization = CancelAuthorization {
+            authorizer: signer.address(),
+            nonce,
+        };
+
+        self.sign_authorization(signer, authorization).await
+    }
+
+    /// Submits a signed [EIP-3009] transfer authorization.
+    ///
+    /// The sender doesn't need to be the authorizer, which allows relaying
+    /// authorizations signed by someone else.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn transfer_with_authorization(
+        &self,
+        signed: &SignedAuthorization<TransferWithAuthorization>,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        let SignedAuthorization {
+            authorization,
+            signature,
+        } = signed;
+
+        IERC3009::new(*self.address(), self.provider())
+            .transferWithAuthorization(
+                authorization.from,
+                authorization.to,
+                authorization.value,
+                authorization.validAfter,
+                authorization.validBefore,
+                authorization.nonce,
+                27 + signature.v() as u8,
+                signature.r().into(),
+                signature.s().into(),
+            )
+            .send()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))
+    }
+
+    /// Submits a signed [EIP-3009] receive authorization.
+    ///
+    /// The sender must be the authorization recipient.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn receive_with_authorization(
+        &self,
+        signed: &SignedAuthorization<ReceiveWithAuthorization>,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        let SignedAuthorization {
+            authorization,
+            signature,
+        } = signed;
+
+        IERC3009::new(*self.address(), self.provider())
+            .receiveWithAuthorization(
+                authorization.from,
+                authorization.to,
+                authorization.value,
+                authorization.validAfter,
+                authorization.validBefore,
+                authorization.nonce,
+                27 + signature.v() as u8,
+                signature.r().into(),
+                signature.s().into(),
+            )
+            .send()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))
+    }
+
+    /// Submits a signed [EIP-3009] authorization cancellation.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn cancel_authorization(
+        &self,
+        signed: &SignedAuthorization<CancelAuthorization>,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        let SignedAuthorization {
+            authorization,
+            signature,
+        } = signed;
+
+        IERC3009::new(*self.address(), self.provider())
+            .cancelAuthorization(
+                authorization.authorizer,
+                authorization.nonce,
+                27 + signature.v() as u8,
+                signature.r().into(),
+                signature.s().into(),
+            )
+            .send()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))
+    }
+
+    async fn sign_authorization<S: Signer, T: SolStruct>(
+        &self,
+        signer: &S,
+        authorization: T,
+    ) -> Result<SignedAuthorization<T>, Error> {
+        let domain = self.eip712_domain().await?;
+
+        let signature = signer
+            .sign_hash(&authorization.eip712_signing_hash(&domain))
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))?;
+
+        Ok(SignedAuthorization {
+            authorization,
+            signature,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{b256, B256, U256},
+        sol_types::SolStruct,
+    };
+
+    use super::{CancelAuthorization, ReceiveWithAuthorization, TransferWithAuthorization};
+
+    #[test]
+    fn test_authorization_typehashes() {
+        let transfer = TransferWithAuthorization {
+            from: Default::default(),
+            to: Default::default(),
+            value: U256::ZERO,
+            validAfter: U256::ZERO,
+            validBefore: U256::ZERO,
+            nonce: B256::ZERO,
+        };
+        let receive = ReceiveWithAuthorization {
+            from: Default::default(),
+            to: Default::default(),
+            value: U256::ZERO,
+            validAfter: U256::ZERO,
+            validBefore: U256::ZERO,
+            nonce: B256::ZERO,
+        };
+        let cancel = CancelAuthorization {
+            authorizer: Default::default(),
+            nonce: B256::ZERO,
+        };
+
+        assert_eq!(
+            transfer.eip712_type_hash(),
+            b256!("7c7c6cdb67a18743f49ec6fa9b35f50d52ed05cbed4cc592e13b44501c1a2267")
+        );
+        assert_eq!(
+            receive.eip712_type_hash(),
+            b256!("d099cc98ef71107a616c4f0f941f04c322d8e254fe26b3c6668db87aae413de8")
+        );
+        assert_eq!(
+            cancel.eip712_type_hash(),
+            b256!("158b0a9edf7a828aad02f63cd515c68ef2f50ba807396f6d12842833a1597429")
+        );
+    }
+}
@@ -29,3 +29,39 @@ pub static USDT: Lazy<Token> = Lazy::<Token>::new(|| {
         6,
     )
 });
+
+/// Circle USD bridged from Ethereum.
+pub static USDC_E: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("FF970A61A04b1cA14834A43f5dE4533eBDDB5CC8"),
+        String::from("USDC.e"),
+        6,
+    )
+});
+
+/// Wrapped Bitcoin.
+pub static WBTC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("2f2a2543B76A4166549F7aaB2e75Bef0aefC5B0f"),
+        String::from("WBTC"),
+        8,
+    )
+});
+
+/// Dai stablecoin.
+pub static DAI: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("DA10009cBd5D07dd0CeCc66161FC93D7c9000da1"),
+        String::from("DAI"),
+        18,
+    )
+});
+
+/// Arbitrum.
+pub static ARB: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("912CE59144191C1204E64559FE8253a0e49E6548"),
+        String::from("ARB"),
+        18,
+    )
+});
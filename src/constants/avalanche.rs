@@ -0,0 +1,49 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped AVAX.
+pub static WAVAX: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("B31f66AA3C1e785363F0875A1B74E27b85FD66c7"),
+        String::from("WAVAX"),
+        18,
+    )
+});
+
+/// Circle USD.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("B97EF9Ef8734C71904D8002F8b6Bc66Dd9c48a6E"),
+        String::from("USDC"),
+        6,
+    )
+});
+
+/// Circle USD bridged from Ethereum.
+pub static USDC_E: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("A7D7079b0FEaD91F3e65f86E8915Cb59c1a4C664"),
+        String::from("USDC.e"),
+        6,
+    )
+});
+
+/// Tether USD.
+pub static USDT: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("9702230A8Ea53601f5cD2dc00fDBc13d4dF4A8c7"),
+        String::from("USDt"),
+        6,
+    )
+});
+
+/// Wrapped Ether bridged from Ethereum.
+pub static WETH_E: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("49D5c2BdFfac6CE2BFdB6640F4F80f226bc10bAB"),
+        String::from("WETH.e"),
+        18,
+    )
+});
@@ -0,0 +1,49 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped Ether.
+pub static WETH: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("4200000000000000000000000000000000000006"),
+        String::from("WETH"),
+        18,
+    )
+});
+
+/// Circle USD.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+        String::from("USDC"),
+        6,
+    )
+});
+
+/// Circle USD bridged from Ethereum.
+pub static USDBC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("d9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA"),
+        String::from("USDbC"),
+        6,
+    )
+});
+
+/// Dai stablecoin.
+pub static DAI: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("50c5725949A6F0c72E6C4a641F24049A917DB0Cb"),
+        String::from("DAI"),
+        18,
+    )
+});
+
+/// Coinbase Wrapped BTC.
+pub static CBBTC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("cbB7C0000aB88B473b1f5aFd9ef808440eed33Bf"),
+        String::from("cbBTC"),
+        8,
+    )
+});
@@ -0,0 +1,49 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped BNB.
+pub static WBNB: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("bb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c"),
+        String::from("WBNB"),
+        18,
+    )
+});
+
+/// Tether USD, with 18 decimals.
+pub static USDT: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("55d398326f99059fF775485246999027B3197955"),
+        String::from("USDT"),
+        18,
+    )
+});
+
+/// Circle USD, with 18 decimals.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("8AC76a51cc950d9822D68b83fE1Ad97B32Cd580d"),
+        String::from("USDC"),
+        18,
+    )
+});
+
+/// Dai stablecoin.
+pub static DAI: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("1AF3F329e8BE154074D8769D1FFa4eE058B1DBc3"),
+        String::from("DAI"),
+        18,
+    )
+});
+
+/// Bitcoin BEP-20.
+pub static BTCB: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("7130d2A12B9BCbFAe4f2634d864A1Ee1Ce3Ead9c"),
+        String::from("BTCB"),
+        18,
+    )
+});
@@ -0,0 +1,49 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped xDAI.
+pub static WXDAI: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("e91D153E0b41518A2Ce8Dd3D7944Fa863463a97d"),
+        String::from("WXDAI"),
+        18,
+    )
+});
+
+/// Wrapped Ether.
+pub static WETH: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("6A023CCd1ff6F2045C3309768eAd9E68F978f6e1"),
+        String::from("WETH"),
+        18,
+    )
+});
+
+/// Circle USD bridged from Ethereum by the Omnibridge.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("DDAfbb505ad214D7b80b1f830fcCc89B60fb7A83"),
+        String::from("USDC"),
+        6,
+    )
+});
+
+/// Circle USD bridged from Ethereum.
+pub static USDC_E: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("2a22f9c3b484c3629090FeED35F17Ff8F88f76F0"),
+        String::from("USDC.e"),
+        6,
+    )
+});
+
+/// Tether USD.
+pub static USDT: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("4ECaBa5870353805a9F068101A40E0f32ed605C6"),
+        String::from("USDT"),
+        6,
+    )
+});
@@ -0,0 +1,40 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped Ether.
+pub static WETH: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("e5D7C2a44FfDDf6b295A15c148167daaAf5Cf34f"),
+        String::from("WETH"),
+        18,
+    )
+});
+
+/// Circle USD.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("176211869cA2b568f2A7D4EE941E073a821EE1ff"),
+        String::from("USDC"),
+        6,
+    )
+});
+
+/// Tether USD.
+pub static USDT: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("A219439258ca9da29E9Cc4cE5596924745e12B93"),
+        String::from("USDT"),
+        6,
+    )
+});
+
+/// Dai stablecoin.
+pub static DAI: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("4AF15ec2A0BD43Db75dd04E62FAA3B8EF36b00d5"),
+        String::from("DAI"),
+        18,
+    )
+});
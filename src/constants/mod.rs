@@ -1,4 +1,29 @@
-/// Arbitrum.
+/// Arbitrum One.
 pub mod arbitrum;
+/// Avalanche C-Chain.
+pub mod avalanche;
+/// Base.
+pub mod base;
+/// BNB Smart Chain.
+pub mod bsc;
+/// Gnosis Chain.
+pub mod gnosis;
+/// Linea.
+pub mod linea;
 /// Ethereum mainnet.
 pub mod mainnet;
+/// Optimism.
+pub mod optimism;
+/// Polygon PoS.
+pub mod polygon;
+/// Scroll.
+pub mod scroll;
+/// Sepolia testnet.
+pub mod sepolia;
+/// zkSync Era.
+pub mod zksync;
+
+#[cfg(feature = "known-tokens")]
+mod registry;
+#[cfg(feature = "known-tokens")]
+pub use registry::{known_chain_ids, known_token, known_tokens};
@@ -0,0 +1,67 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped Ether.
+pub static WETH: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("4200000000000000000000000000000000000006"),
+        String::from("WETH"),
+        18,
+    )
+});
+
+/// Circle USD.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("0b2C639c533813f4Aa9D7837CAf62653d097Ff85"),
+        String::from("USDC"),
+        6,
+    )
+});
+
+/// Circle USD bridged from Ethereum.
+pub static USDC_E: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("7F5c764cBc14f9669B88837ca1490cCa17c31607"),
+        String::from("USDC.e"),
+        6,
+    )
+});
+
+/// Tether USD.
+pub static USDT: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("94b008aA00579c1307B0EF2c499aD98a8ce58e58"),
+        String::from("USDT"),
+        6,
+    )
+});
+
+/// Dai stablecoin.
+pub static DAI: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("DA10009cBd5D07dd0CeCc66161FC93D7c9000da1"),
+        String::from("DAI"),
+        18,
+    )
+});
+
+/// Wrapped Bitcoin.
+pub static WBTC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("68f180fcCe6836688e9084f035309E29Bf0A2095"),
+        String::from("WBTC"),
+        8,
+    )
+});
+
+/// Optimism.
+pub static OP: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("4200000000000000000000000000000000000042"),
+        String::from("OP"),
+        18,
+    )
+});
@@ -0,0 +1,67 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped POL, formerly Wrapped Matic.
+pub static WPOL: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270"),
+        String::from("WPOL"),
+        18,
+    )
+});
+
+/// Wrapped Ether.
+pub static WETH: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("7ceB23fD6bC0adD59E62ac25578270cFf1b9f619"),
+        String::from("WETH"),
+        18,
+    )
+});
+
+/// Circle USD.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("3c499c542cEF5E3811e1192ce70d8cC03d5c3359"),
+        String::from("USDC"),
+        6,
+    )
+});
+
+/// Circle USD bridged from Ethereum.
+pub static USDC_E: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("2791Bca1f2de4661ED88A30C99A7a9449Aa84174"),
+        String::from("USDC.e"),
+        6,
+    )
+});
+
+/// Tether USD.
+pub static USDT: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("c2132D05D31c914a87C6611C10748AEb04B58e8F"),
+        String::from("USDT"),
+        6,
+    )
+});
+
+/// Dai stablecoin.
+pub static DAI: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("8f3Cf7ad23Cd3CaDbD9735AFf958023239c6A063"),
+        String::from("DAI"),
+        18,
+    )
+});
+
+/// Wrapped Bitcoin.
+pub static WBTC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("1BFD67037B42Cf73acF2047067bd4F2C47D9BfD6"),
+        String::from("WBTC"),
+        8,
+    )
+});
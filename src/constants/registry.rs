@@ -0,0 +1,167 @@
+use once_cell::sync::Lazy;
+
+use crate::{Token, TokenId};
+
+use super::{
+    arbitrum, avalanche, base, bsc, gnosis, linea, mainnet, optimism, polygon, scroll, sepolia,
+    zksync,
+};
+
+type KnownTokens = [&'static Lazy<Token>];
+
+static MAINNET: &KnownTokens = &[
+    &mainnet::WETH,
+    &mainnet::WBTC,
+    &mainnet::USDC,
+    &mainnet::USDT,
+    &mainnet::DAI,
+];
+
+static OPTIMISM: &KnownTokens = &[
+    &optimism::WETH,
+    &optimism::USDC,
+    &optimism::USDC_E,
+    &optimism::USDT,
+    &optimism::DAI,
+    &optimism::WBTC,
+    &optimism::OP,
+];
+
+static BSC: &KnownTokens = &[&bsc::WBNB, &bsc::USDT, &bsc::USDC, &bsc::DAI, &bsc::BTCB];
+
+static GNOSIS: &KnownTokens = &[
+    &gnosis::WXDAI,
+    &gnosis::WETH,
+    &gnosis::USDC,
+    &gnosis::USDC_E,
+    &gnosis::USDT,
+];
+
+static POLYGON: &KnownTokens = &[
+    &polygon::WPOL,
+    &polygon::WETH,
+    &polygon::USDC,
+    &polygon::USDC_E,
+    &polygon::USDT,
+    &polygon::DAI,
+    &polygon::WBTC,
+];
+
+static ZKSYNC: &KnownTokens = &[&zksync::WETH, &zksync::USDC, &zksync::USDC_E, &zksync::USDT];
+
+static BASE: &KnownTokens = &[
+    &base::WETH,
+    &base::USDC,
+    &base::USDBC,
+    &base::DAI,
+    &base::CBBTC,
+];
+
+static ARBITRUM: &KnownTokens = &[
+    &arbitrum::WETH,
+    &arbitrum::USDC,
+    &arbitrum::USDC_E,
+    &arbitrum::USDT,
+    &arbitrum::WBTC,
+    &arbitrum::DAI,
+    &arbitrum::ARB,
+];
+
+static AVALANCHE: &KnownTokens = &[
+    &avalanche::WAVAX,
+    &avalanche::USDC,
+    &avalanche::USDC_E,
+    &avalanche::USDT,
+    &avalanche::WETH_E,
+];
+
+static LINEA: &KnownTokens = &[&linea::WETH, &linea::USDC, &linea::USDT, &linea::DAI];
+
+static SCROLL: &KnownTokens = &[&scroll::WETH, &scroll::USDC, &scroll::USDT];
+
+static SEPOLIA: &KnownTokens = &[&sepolia::WETH, &sepolia::USDC, &sepolia::LINK];
+
+static CHAIN_IDS: &[u64] = &[
+    1, 10, 56, 100, 137, 324, 8453, 42161, 43114, 59144, 534352, 11155111,
+];
+
+fn chain_tokens(chain_id: u64) -> &'static KnownTokens {
+    match chain_id {
+        1 => MAINNET,
+        10 => OPTIMISM,
+        56 => BSC,
+        100 => GNOSIS,
+        137 => POLYGON,
+        324 => ZKSYNC,
+        8453 => BASE,
+        42161 => ARBITRUM,
+        43114 => AVALANCHE,
+        59144 => LINEA,
+        534352 => SCROLL,
+        11155111 => SEPOLIA,
+        _ => &[],
+    }
+}
+
+/// Returns the ids of the chains with known tokens.
+pub fn known_chain_ids() -> &'static [u64] {
+    CHAIN_IDS
+}
+
+/// Returns the known tokens of a chain: its wrapped native token, canonical
+/// stablecoins and their bridged variants, and a few major tokens.
+///
+/// Returns no token for unknown chains.
+pub fn known_tokens(chain_id: u64) -> impl Iterator<Item = &'static Token> {
+    chain_tokens(chain_id).iter().map(|token| &***token)
+}
+
+/// Returns a known token of a chain, by address or symbol.
+///
+/// Bridged variants have distinct symbols, like `USDC.e`, so symbols are
+/// unique on each chain.
+pub fn known_token(chain_id: u64, id: impl Into<TokenId>) -> Option<&'static Token> {
+    let id = id.into();
+
+    known_tokens(chain_id).find(|token| match &id {
+        TokenId::Address(address) => token.address == *address,
+        TokenId::Symbol(symbol) => token.symbol == *symbol,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{known_chain_ids, known_token, known_tokens};
+    use crate::{arbitrum, mainnet, TokenId};
+
+    #[test]
+    fn test_known_tokens_are_unique() {
+        for chain_id in known_chain_ids() {
+            let tokens = known_tokens(*chain_id).collect::<Vec<_>>();
+            let addresses = tokens.iter().map(|t| t.address).collect::<HashSet<_>>();
+            let symbols = tokens.iter().map(|t| &t.symbol).collect::<HashSet<_>>();
+
+            assert!(!tokens.is_empty(), "no token on chain {chain_id}");
+            assert_eq!(addresses.len(), tokens.len(), "chain {chain_id}");
+            assert_eq!(symbols.len(), tokens.len(), "chain {chain_id}");
+        }
+
+        assert_eq!(known_tokens(31337).count(), 0);
+    }
+
+    #[test]
+    fn test_known_token() {
+        assert_eq!(
+            known_token(1, TokenId::Symbol("USDC".to_string())).unwrap(),
+            &*mainnet::USDC
+        );
+        assert_eq!(
+            known_token(42161, arbitrum::USDC_E.address).unwrap().symbol,
+            "USDC.e"
+        );
+        assert!(known_token(42161, mainnet::USDC.address).is_none());
+        assert!(known_token(1, TokenId::Symbol("USDC.e".to_string())).is_none());
+    }
+}
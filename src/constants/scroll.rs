@@ -0,0 +1,31 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped Ether.
+pub static WETH: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("5300000000000000000000000000000000000004"),
+        String::from("WETH"),
+        18,
+    )
+});
+
+/// Circle USD.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("06eFdBFf2a14a7c8E15944D1F4A48F9F95F663A4"),
+        String::from("USDC"),
+        6,
+    )
+});
+
+/// Tether USD.
+pub static USDT: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("f55BEC9cafDbE8730f096Aa55dad6D22d44099Df"),
+        String::from("USDT"),
+        6,
+    )
+});
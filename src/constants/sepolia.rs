@@ -0,0 +1,31 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped Ether.
+pub static WETH: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("fFf9976782d46CC05630D1f6eBAb18b2324d6B14"),
+        String::from("WETH"),
+        18,
+    )
+});
+
+/// Circle USD.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("1c7D4B196Cb0C7B01d743Fbc6116a902379C7238"),
+        String::from("USDC"),
+        6,
+    )
+});
+
+/// Chainlink token.
+pub static LINK: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("779877A7B0D9E8603169DdbD7836e478b4624789"),
+        String::from("LINK"),
+        18,
+    )
+});
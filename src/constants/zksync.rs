@@ -0,0 +1,40 @@
+use alloy::primitives::address;
+use once_cell::sync::Lazy;
+
+use crate::Token;
+
+/// Wrapped Ether.
+pub static WETH: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("5AEa5775959fBC2557Cc8789bC1bf90A239D9a91"),
+        String::from("WETH"),
+        18,
+    )
+});
+
+/// Circle USD.
+pub static USDC: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("1d17CBcF0D6D143135aE902365D2E5e2A16538D4"),
+        String::from("USDC"),
+        6,
+    )
+});
+
+/// Circle USD bridged from Ethereum.
+pub static USDC_E: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("3355df6D4c9C3035724Fd0e3914dE96A5a83aaf4"),
+        String::from("USDC.e"),
+        6,
+    )
+});
+
+/// Tether USD.
+pub static USDT: Lazy<Token> = Lazy::<Token>::new(|| {
+    Token::new(
+        address!("493257fD37EDB34451f62EDf8D2a0C418852bA4C"),
+        String::from("USDT"),
+        6,
+    )
+});
@@ -0,0 +1,283 @@
+use alloy::{
+    contract,
+    network::Network,
+    primitives::{fixed_bytes, Address, Bytes, FixedBytes, U256},
+    providers::{PendingTransactionBuilder, Provider},
+    sol,
+};
+
+use crate::{Error, LazyToken, LazyTokenSigner};
+
+sol! {
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IERC1363 {
+        function transferAndCall(address to, uint256 value) external returns (bool);
+        function transferAndCall(address to, uint256 value, bytes calldata data) external returns (bool);
+        function transferFromAndCall(address from, address to, uint256 value) external returns (bool);
+        function transferFromAndCall(address from, address to, uint256 value, bytes calldata data) external returns (bool);
+        function approveAndCall(address spender, uint256 value) external returns (bool);
+        function approveAndCall(address spender, uint256 value, bytes calldata data) external returns (bool);
+    }
+
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IERC165 {
+        function supportsInterface(bytes4 interfaceId) external view returns (bool);
+    }
+}
+
+/// The [ERC-165] interface id of ERC-165 itself.
+///
+/// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+pub const ERC165_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("01ffc9a7");
+
+/// The [ERC-165] interface id of [ERC-1363].
+///
+/// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+/// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+pub const ERC1363_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("b0202a11");
+
+/// An interface id that no ERC-165 contract may support.
+pub(crate) const INVALID_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("ffffffff");
+
+impl<P, N> LazyToken<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Returns `true` if the token implements [ERC-165] and supports
+    /// `interface_id`.
+    ///
+    /// Tokens not implementing ERC-165, whose `supportsInterface` reverts or
+    /// doesn't exist, are reported as not supporting any interface, as is
+    /// the native asset.
+    ///
+    /// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+    pub async fn supports_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Error> {
+        if self.is_native() {
+            return Ok(false);
+        }
+
+        Ok(self.query_interface(ERC165_INTERFACE_ID).await?
+            && !self.query_interface(INVALID_INTERFACE_ID).await?
+            && self.query_interface(interface_id).await?)
+    }
+
+    /// Returns `true` if the token supports [ERC-1363], as advertised
+    /// through [ERC-165].
+    ///
+    /// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+    /// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+    pub async fn supports_erc1363(&self) -> Result<bool, Error> {
+        self.supports_interface(ERC1363_INTERFACE_ID).await
+    }
+
+    /// Calls `supportsInterface`, mapping reverts and invalid return data
+    /// to `false`.
+    async fn query_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Error> {
+        match IERC165::new(*self.address(), self.provider())
+            .supportsInterface(interface_id)
+            .call()
+            .await
+        {
+            Ok(supported) => Ok(supported),
+            Err(contract::Error::TransportError(err)) if err.as_error_resp().is_none() => {
+                Err(self.error(err))
+            }
+            Err(_) => Ok(false),
+        }
+    }
+}
+
+impl<P, N> LazyTokenSigner<P, N>
+where
+    P: Provider<N> + Clone,
+    N: Network,
+{
+    /// Returns `true` if the token implements [ERC-165] and supports
+    /// `interface_id`.
+    ///
+    /// See [`LazyToken::supports_interface`].
+    ///
+    /// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+    pub async fn supports_interface(&self, interface_id: FixedBytes<4>) -> Result<bool, Error> {
+        self.token.supports_interface(interface_id).await
+    }
+
+    /// Returns `true` if the token supports [ERC-1363].
+    ///
+    /// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+    pub async fn supports_erc1363(&self) -> Result<bool, Error> {
+        self.token.supports_erc1363().await
+    }
+
+    /// Transfers `value` tokens to `to`, then calls `onTransferReceived` on
+    /// `to`, with [ERC-1363].
+    ///
+    /// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn transfer_and_call(
+        &self,
+        to: Address,
+        value: U256,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.token.ensure_erc20("transferAndCall")?;
+
+        IERC1363::new(*self.address(), self.provider())
+            .transferAndCall_0(to, value)
+            .send()
+            .await
+            .map_err(|err| self.token.error(err))
+    }
+
+    /// Transfers `value` tokens to `to`, then calls `onTransferReceived` on
+    /// `to` with `data`, with [ERC-1363].
+    ///
+    /// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn transfer_and_call_with_data(
+        &self,
+        to: Address,
+        value: U256,
+        data: Bytes,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.token.ensure_erc20("transferAndCall")?;
+
+        IERC1363::new(*self.address(), self.provider())
+            .transferAndCall_1(to, value, data)
+            .send()
+            .await
+            .map_err(|err| self.token.error(err))
+    }
+
+    /// Transfers `value` tokens from `from` to `to` using the allowance
+    /// mechanism, then calls `onTransferReceived` on `to`, with [ERC-1363].
+    ///
+    /// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn transfer_from_and_call(
+        &self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.token.ensure_erc20("transferFromAndCall")?;
+
+        IERC1363::new(*self.address(), self.provider())
+            .transferFromAndCall_0(from, to, value)
+            .send()
+            .await
+            .map_err(|err| self.token.error(err))
+    }
+
+    /// Transfers `value` tokens from `from` to `to` using the allowance
+    /// mechanism, then calls `onTransferReceived` on `to` with `data`, with
+    /// [ERC-1363].
+    ///
+    /// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn transfer_from_and_call_with_data(
+        &self,
+        from: Address,
+        to: Address,
+        value: U256,
+        data: Bytes,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.token.ensure_erc20("transferFromAndCall")?;
+
+        IERC1363::new(*self.address(), self.provider())
+            .transferFromAndCall_1(from, to, value, data)
+            .send()
+            .await
+            .map_err(|err| self.token.error(err))
+    }
+
+    /// Approves `spender` to transfer up to `value` tokens on behalf of the
+    /// caller, then calls `onApprovalReceived` on `spender`, with
+    /// [ERC-1363].
+    ///
+    /// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn approve_and_call(
+        &self,
+        spender: Address,
+        value: U256,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.token.ensure_erc20("approveAndCall")?;
+
+        IERC1363::new(*self.address(), self.provider())
+            .approveAndCall_0(spender, value)
+            .send()
+            .await
+            .map_err(|err| self.token.error(err))
+    }
+
+    /// Approves `spender` to transfer up to `value` tokens on behalf of the
+    /// caller, then calls `onApprovalReceived` on `spender` with `data`,
+    /// with [ERC-1363].
+    ///
+    /// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn approve_and_call_with_data(
+        &self,
+        spender: Address,
+        value: U256,
+        data: Bytes,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.token.ensure_erc20("approveAndCall")?;
+
+        IERC1363::new(*self.address(), self.provider())
+            .approveAndCall_1(spender, value, data)
+            .send()
+            .await
+            .map_err(|err| self.token.error(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{primitives::FixedBytes, sol_types::SolCall};
+
+    use super::{ERC1363_INTERFACE_ID, ERC165_INTERFACE_ID, IERC1363, IERC165};
+
+    #[test]
+    fn test_interface_ids() {
+        let erc1363 = [
+            IERC1363::transferAndCall_0Call::SELECTOR,
+            IERC1363::transferAndCall_1Call::SELECTOR,
+            IERC1363::transferFromAndCall_0Call::SELECTOR,
+            IERC1363::transferFromAndCall_1Call::SELECTOR,
+            IERC1363::approveAndCall_0Call::SELECTOR,
+            IERC1363::approveAndCall_1Call::SELECTOR,
+        ]
+        .into_iter()
+        .fold([0; 4], |id, selector| {
+            std::array::from_fn(|i| id[i] ^ selector[i])
+        });
+
+        assert_eq!(FixedBytes(erc1363), ERC1363_INTERFACE_ID);
+        assert_eq!(
+            FixedBytes(IERC165::supportsInterfaceCall::SELECTOR),
+            ERC165_INTERFACE_ID
+        );
+    }
+}
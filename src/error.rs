@@ -28,14 +28,83 @@ impl Display for Error {
 }
 
 /// Token related possible errors
+///
+/// New variants may be added in minor releases.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum InternalError {
+    /// The token is not present in the store.
     #[error("The token {0} is not present in the store")]
     NotInStore(String),
+    /// The RPC request failed.
     #[error("Failed to query token: {0}")]
     Transport(#[from] alloy::transports::TransportError),
+    /// The contract call failed, boxed as it is much larger than the other
+    /// errors.
     #[error("Contract error: {0}")]
-    Contract(#[from] alloy::contract::Error),
+    Contract(#[source] Box<alloy::contract::Error>),
+    /// The contract returned data that couldn't be decoded.
     #[error("Failed to decode token: {0}")]
     Sol(#[from] alloy::sol_types::Error),
+    /// A batched call failed, with the given return data.
+    #[error("Call failed with return data: {0}")]
+    CallFailed(alloy::primitives::Bytes),
+    /// Waiting for a transaction to be confirmed failed.
+    #[error("Failed to confirm transaction: {0}")]
+    PendingTransaction(#[from] alloy::providers::PendingTransactionError),
+    /// The token returned `false` instead of reverting.
+    #[error("The token returned false")]
+    ReturnedFalse,
+    /// An ERC-20 function was called on the native asset, which has no
+    /// contract.
+    #[error("The native asset has no {0} function")]
+    NativeAsset(&'static str),
+    /// The token address has no code, so calls to it always succeed.
+    #[error("The token has no code")]
+    NoCode,
+    /// The transaction was included, but reverted.
+    #[error("Transaction {0} reverted")]
+    Reverted(alloy::primitives::TxHash),
+    /// Signing failed.
+    #[error("Failed to sign: {0}")]
+    Signer(#[from] alloy::signers::Error),
+    /// No known EIP-712 domain matches the token domain separator.
+    #[error("No known EIP-712 domain matches the domain separator {0}")]
+    UnknownDomain(alloy::primitives::B256),
+    /// An amount of another token was given.
+    #[error("Expected an amount of this token, got an amount of {0}")]
+    TokenMismatch(alloy::primitives::Address),
+    /// An amount computation overflowed.
+    #[error("Amount overflow")]
+    Overflow,
+    /// An amount subtraction went below zero.
+    #[error("Amount underflow")]
+    Underflow,
+    /// An amount has more decimals than the token.
+    #[error("Amount {0} has more decimals than the token")]
+    ExcessPrecision(String),
+    /// An amount couldn't be parsed, or is invalid for the token.
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    /// Several tokens share the symbol on the chain.
+    #[error("Ambiguous symbol, matching tokens: {0:?}")]
+    AmbiguousSymbol(Vec<alloy::primitives::Address>),
+    /// The symbol is already used by another token in the store.
+    #[error("The symbol is already used by {0}")]
+    SymbolConflict(alloy::primitives::Address),
+    /// The symbol can't be resolved as an ENS name.
+    #[error("Invalid ENS name: {0}")]
+    InvalidEnsName(String),
+    /// A fetch awaited by concurrent lookups failed, with the given error.
+    #[error("Shared fetch failed: {0}")]
+    FetchFailed(std::sync::Arc<crate::Error>),
+    /// The store failed to persist the token.
+    #[error("Failed to write to the store: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<alloy::contract::Error> for InternalError {
+    fn from(err: alloy::contract::Error) -> Self {
+        Self::Contract(Box::new(err))
+    }
 }
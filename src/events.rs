@@ -0,0 +1,337 @@
+use std::{collections::VecDeque, ops::RangeInclusive};
+
+use alloy::{
+    network::Network,
+    primitives::{Address, TxHash, U256},
+    providers::Provider,
+    rpc::{
+        json_rpc::ErrorPayload,
+        types::{Filter, Log},
+    },
+    sol_types::SolEvent,
+    transports::TransportError,
+};
+use bigdecimal::BigDecimal;
+use futures::{
+    stream::{self, BoxStream},
+    Stream, StreamExt,
+};
+
+use crate::{provider::Erc20Contract, Error, LazyToken, Token, TokenId};
+
+/// A decoded ERC-20 `Transfer` event.
+#[derive(Debug, Clone)]
+pub struct TransferEvent {
+    /// The transferred token.
+    pub token: Token,
+    /// The sender.
+    pub from: Address,
+    /// The recipient.
+    pub to: Address,
+    /// The transferred amount, in base units.
+    pub value: U256,
+    /// The block the event was emitted in, if mined.
+    pub block_number: Option<u64>,
+    /// The transaction the event was emitted by, if mined.
+    pub transaction_hash: Option<TxHash>,
+    /// The event index in its block, if mined.
+    pub log_index: Option<u64>,
+}
+
+impl TransferEvent {
+    /// Decodes a `Transfer` log emitted by `token`.
+    pub fn decode(token: Token, log: &Log) -> Result<Self, alloy::sol_types::Error> {
+        let event = log.log_decode::<Erc20Contract::Transfer>()?.inner.data;
+
+        Ok(Self {
+            token,
+            from: event.from,
+            to: event.to,
+            value: event.value,
+            block_number: log.block_number,
+            transaction_hash: log.transaction_hash,
+            log_index: log.log_index,
+        })
+    }
+
+    /// Gets the transferred amount as a [`BigDecimal`].
+    pub fn amount(&self) -> BigDecimal {
+        self.token.get_balance(self.value)
+    }
+}
+
+/// A decoded ERC-20 `Approval` event.
+#[derive(Debug, Clone)]
+pub struct ApprovalEvent {
+    /// The approved token.
+    pub token: Token,
+    /// The tokens owner.
+    pub owner: Address,
+    /// The approved spender.
+    pub spender: Address,
+    /// The approved amount, in base units.
+    pub value: U256,
+    /// The block the event was emitted in, if mined.
+    pub block_number: Option<u64>,
+    /// The transaction the event was emitted by, if mined.
+    pub transaction_hash: Option<TxHash>,
+    /// The event index in its block, if mined.
+    pub log_index: Option<u64>,
+}
+
+impl ApprovalEvent {
+    /// Decodes an `Approval` log emitted by `token`.
+    pub fn decode(token: Token, log: &Log) -> Result<Self, alloy::sol_types::Error> {
+        let event = log.log_decode::<Erc20Contract::Approval>()?.inner.data;
+
+        Ok(Self {
+            token,
+            owner: event.owner,
+            spender: event.spender,
+            value: event.value,
+            block_number: log.block_number,
+            transaction_hash: log.transaction_hash,
+            log_index: log.log_index,
+        })
+    }
+
+    /// Gets the approved amount as a [`BigDecimal`].
+    pub fn amount(&self) -> BigDecimal {
+        self.token.get_balance(self.value)
+    }
+}
+
+/// Filters `Transfer` events by sender and recipient.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransferFilter {
+    from: Option<Address>,
+    to: Option<Address>,
+}
+
+impl TransferFilter {
+    /// Creates a new [`TransferFilter`], matching any transfer.
+    pub const fn new() -> Self {
+        Self {
+            from: None,
+            to: None,
+        }
+    }
+
+    /// Only matches transfers sent by `from`.
+    pub const fn from(mut self, from: Address) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Only matches transfers received by `to`.
+    pub const fn to(mut self, to: Address) -> Self {
+        self.to = Some(to);
+        self
+    }
+
+    /// Restricts `filter` to the matching transfers.
+    pub(crate) fn apply(&self, mut filter: Filter) -> Filter {
+        if let Some(from) = self.from {
+            filter = filter.topic1(from.into_word());
+        }
+
+        if let Some(to) = self.to {
+            filter = filter.topic2(to.into_word());
+        }
+
+        filter
+    }
+}
+
+impl<P, N> LazyToken<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Watches the `Transfer` events matching `filter`, as they are mined.
+    ///
+    /// Logs are subscribed to with `eth_subscribe` when the `pubsub` feature
+    /// is enabled and the transport supports it, and polled with a filter
+    /// otherwise.
+    pub async fn watch_transfers(
+        &self,
+        filter: TransferFilter,
+    ) -> Result<BoxStream<'_, Result<TransferEvent, Error>>, Error> {
+        let id = TokenId::from(*self.address());
+        let symbol = self.symbol().await?;
+        let decimals = self.decimals().await?;
+        let token = Token::new(*self.address(), symbol.clone(), *decimals);
+        let filter = filter.apply(event_filter::<Erc20Contract::Transfer>(*self.address()));
+
+        let decode = move |log: Log| {
+            TransferEvent::decode(token.clone(), &log).map_err(|err| Error::new(id.clone(), err))
+        };
+
+        #[cfg(feature = "pubsub")]
+        if let Ok(subscription) = self.provider().subscribe_logs(&filter).await {
+            return Ok(subscription.into_stream().map(decode).boxed());
+        }
+
+        let poller = self
+            .provider()
+            .watch_logs(&filter)
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))?;
+
+        Ok(poller
+            .into_stream()
+            .flat_map(stream::iter)
+            .map(decode)
+            .boxed())
+    }
+}
+
+/// Returns a filter matching the `E` events emitted by `token`.
+pub(crate) fn event_filter<E: SolEvent>(token: Address) -> Filter {
+    Filter::new()
+        .address(token)
+        .event_signature(E::SIGNATURE_HASH)
+}
+
+/// Streams the logs matching `filter` over `blocks`, in order.
+///
+/// When the node rejects a query because its block range or result set is
+/// too large, see [`is_range_error`], the range is split in halves that are
+/// queried in turn. Other errors end the stream.
+pub(crate) fn logs<'a, P, N>(
+    provider: &'a P,
+    filter: Filter,
+    blocks: RangeInclusive<u64>,
+) -> impl Stream<Item = Result<Log, TransportError>> + Send + 'a
+where
+    P: Provider<N>,
+    N: Network,
+{
+    let pending = if blocks.is_empty() {
+        vec![]
+    } else {
+        vec![(*blocks.start(), *blocks.end())]
+    };
+
+    stream::try_unfold(
+        (pending, VecDeque::new()),
+        move |(mut pending, mut buffer)| {
+            let filter = filter.clone();
+
+            async move {
+                loop {
+                    if let Some(log) = buffer.pop_front() {
+                        return Ok(Some((log, (pending, buffer))));
+                    }
+
+                    let Some((from, to)) = pending.pop() else {
+                        return Ok(None);
+                    };
+
+                    match provider
+                        .get_logs(&filter.clone().from_block(from).to_block(to))
+                        .await
+                    {
+                        Ok(logs) => buffer.extend(logs),
+                        Err(err)
+                            if from < to && err.as_error_resp().is_some_and(is_range_error) =>
+                        {
+                            let middle = from + (to - from) / 2;
+                            pending.push((middle + 1, to));
+                            pending.push((from, middle));
+                        }
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+        },
+    )
+}
+
+/// Messages used by nodes and providers to reject `eth_getLogs` queries
+/// covering too many blocks or returning too many logs, lowercased.
+const RANGE_ERRORS: &[&str] = &[
+    "range too large",
+    "range is too large",
+    "range is too wide",
+    "exceed maximum block range",
+    "too many blocks",
+    "too many results",
+    "query returned more than",
+    "response size exceeded",
+    "response size should not",
+    "log response size",
+    "limited to a",
+];
+
+/// Returns `true` if `error` rejects a logs query because of its block range
+/// or result set size, meaning a smaller range may succeed.
+///
+/// Besides the known messages, the `-32005` "limit exceeded" code used by
+/// Infura for this purpose is recognized.
+pub(crate) fn is_range_error(error: &ErrorPayload) -> bool {
+    let message = error.message.to_lowercase();
+
+    error.code == -32005 || RANGE_ERRORS.iter().any(|pattern| message.contains(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::Address,
+        providers::ProviderBuilder,
+        rpc::{json_rpc::ErrorPayload, types::Log},
+        transports::mock::Asserter,
+    };
+    use futures::TryStreamExt;
+
+    use super::{event_filter, is_range_error, logs};
+    use crate::provider::Erc20Contract;
+
+    fn error(code: i64, message: &'static str) -> ErrorPayload {
+        ErrorPayload {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_is_range_error() {
+        assert!(is_range_error(&error(
+            -32000,
+            "query returned more than 10000 results"
+        )));
+        assert!(is_range_error(&error(-32602, "Block range is too large")));
+        assert!(is_range_error(&error(-32005, "limit exceeded")));
+        assert!(!is_range_error(&error(-32000, "execution reverted")));
+        assert!(!is_range_error(&error(-32601, "method not found")));
+    }
+
+    #[tokio::test]
+    async fn test_logs_splits_on_range_errors_only() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let filter = event_filter::<Erc20Contract::Transfer>(Address::ZERO);
+
+        asserter.push_failure(error(-32000, "query returned more than 10000 results"));
+        asserter.push_success(&Vec::<Log>::new());
+        asserter.push_success(&Vec::<Log>::new());
+
+        let events = logs(&provider, filter.clone(), 0..=9)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+        assert!(asserter.read_q().is_empty());
+
+        asserter.push_failure(error(-32601, "method not found"));
+        asserter.push_success(&Vec::<Log>::new());
+
+        assert!(logs(&provider, filter, 0..=9)
+            .try_collect::<Vec<_>>()
+            .await
+            .is_err());
+        assert_eq!(asserter.read_q().len(), 1);
+    }
+}
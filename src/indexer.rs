@@ -0,0 +1,424 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    ops::RangeInclusive,
+};
+
+use alloy::{
+    eips::BlockId,
+    network::Network,
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use futures::TryStreamExt;
+
+use crate::{events, provider::Erc20Contract, Error, LazyToken, TokenId};
+
+/// The balances of the indexed holders after a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceSnapshot {
+    /// The block the balances are taken after.
+    pub block_number: u64,
+    /// The balance of each indexed holder, in base units.
+    pub balances: HashMap<Address, U256>,
+}
+
+/// The balance history of a set of holders, replayed from `Transfer` logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceHistory {
+    /// The replayed blocks.
+    pub blocks: RangeInclusive<u64>,
+    /// The balances before the first replayed block.
+    pub initial: HashMap<Address, U256>,
+    /// A snapshot for every block changing the balance of a holder, in
+    /// order.
+    pub snapshots: Vec<BalanceSnapshot>,
+    /// The transfers debiting more than the replayed balance of a holder,
+    /// in order, see [`BalanceIndexer::replay`].
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl BalanceHistory {
+    /// Returns the balance of `holder` after `block`, or `None` if `holder`
+    /// isn't indexed or `block` wasn't replayed.
+    pub fn balance_at(&self, holder: Address, block: u64) -> Option<U256> {
+        if !self.blocks.contains(&block) {
+            return None;
+        }
+
+        let index = self
+            .snapshots
+            .partition_point(|snapshot| snapshot.block_number <= block);
+
+        match index.checked_sub(1) {
+            Some(index) => self.snapshots[index].balances.get(&holder).copied(),
+            None => self.initial.get(&holder).copied(),
+        }
+    }
+}
+
+/// A replayed balance not matching the `balanceOf` of a holder at the same
+/// block.
+///
+/// This is expected from tokens whose balances change without emitting
+/// `Transfer` events, like rebasing tokens, or emitting events that don't
+/// match the transferred amounts, like some fee-on-transfer tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Discrepancy {
+    /// The holder.
+    pub holder: Address,
+    /// The block the balances are compared at.
+    pub block_number: u64,
+    /// The balance replayed from `Transfer` logs.
+    pub expected: U256,
+    /// The balance returned by `balanceOf`.
+    pub actual: U256,
+}
+
+/// Reconstructs the balance history of a set of holders from the token
+/// `Transfer` logs.
+///
+/// # Examples
+///
+/// ```no_run
+/// use alloy::primitives::address;
+/// use alloy::providers::ProviderBuilder;
+/// use alloy_erc20::{BalanceIndexer, LazyToken};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new().connect_http("https://eth.llamarpc.com".parse()?);
+///
+/// let token = LazyToken::new(
+///     address!("6B175474E89094C44Da98b954EedeAC495271d0F"), // DAI
+///     provider,
+/// );
+/// let indexer = BalanceIndexer::new(
+///     token,
+///     [address!("70997970C51812dc3A010C7d01b50e0d17dc79C8")],
+/// );
+///
+/// let history = indexer.replay(19_000_000..=19_001_000).await?;
+///
+/// for discrepancy in indexer.verify(&history).await? {
+///     println!("{discrepancy:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BalanceIndexer<P, N> {
+    token: LazyToken<P, N>,
+    holders: BTreeSet<Address>,
+}
+
+impl<P, N> BalanceIndexer<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Creates a new [`BalanceIndexer`] of `holders`.
+    pub fn new(token: LazyToken<P, N>, holders: impl IntoIterator<Item = Address>) -> Self {
+        Self {
+            token,
+            holders: holders.into_iter().collect(),
+        }
+    }
+
+    /// Returns the indexed token.
+    pub const fn token(&self) -> &LazyToken<P, N> {
+        &self.token
+    }
+
+    /// Returns the indexed holders.
+    pub const fn holders(&self) -> &BTreeSet<Address> {
+        &self.holders
+    }
+
+    /// Replays the `Transfer` logs of the holders over `blocks`.
+    ///
+    /// The initial balances are read with `balanceOf` at the block before
+    /// the range, which requires an archive node for old blocks.
+    ///
+    /// A transfer debiting more than the replayed balance of a holder is
+    /// reported in [`BalanceHistory::discrepancies`], with the balance it
+    /// was debited from as `expected`. The balance of the holder is then
+    /// read with `balanceOf` at the block of the transfer, the replay
+    /// resuming from it after that block.
+    pub async fn replay(&self, blocks: RangeInclusive<u64>) -> Result<BalanceHistory, Error> {
+        let mut initial = HashMap::with_capacity(self.holders.len());
+
+        for holder in &self.holders {
+            let balance = match blocks.start().checked_sub(1) {
+                Some(block) => self.balance_of(*holder, block).await?,
+                None => U256::ZERO,
+            };
+            initial.insert(*holder, balance);
+        }
+
+        let holders = self
+            .holders
+            .iter()
+            .map(|holder| holder.into_word())
+            .collect::<Vec<_>>();
+        let filter = events::event_filter::<Erc20Contract::Transfer>(*self.token.address());
+
+        // Ordered by position in the chain, and deduplicated, as transfers
+        // between holders match both filters.
+        let mut logs = BTreeMap::new();
+
+        for filter in [
+            filter.clone().topic1(holders.clone()),
+            filter.topic2(holders),
+        ] {
+            let mut stream =
+                std::pin::pin!(events::logs(self.token.provider(), filter, blocks.clone()));
+
+            while let Some(log) = stream.try_next().await.map_err(|err| self.error(err))? {
+                if let (Some(block), Some(index)) = (log.block_number, log.log_index) {
+                    logs.insert((block, index), log);
+                }
+            }
+        }
+
+        let transfers = logs
+            .into_iter()
+            .map(|((block, _), log)| {
+                let transfer = log
+                    .log_decode::<Erc20Contract::Transfer>()
+                    .map_err(|err| self.error(err))?
+                    .inner
+                    .data;
+                Ok((block, transfer.from, transfer.to, transfer.value))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // Replayed again with the balance of every holder that couldn't
+        // cover a transfer, until each is known
+        let mut synced = HashMap::new();
+
+        let (snapshots, discrepancies) = loop {
+            match replay(&initial, &transfers, &synced) {
+                Ok(replayed) => break replayed,
+                Err((block, holder)) => {
+                    let balance = self.balance_of(holder, block).await?;
+                    synced.insert((block, holder), balance);
+                }
+            }
+        };
+
+        Ok(BalanceHistory {
+            blocks,
+            initial,
+            snapshots,
+            discrepancies,
+        })
+    }
+
+    /// Compares every snapshot of `history` against `balanceOf` at the same
+    /// block, returning the mismatching balances.
+    ///
+    /// The final balances are compared too, even if no transfer happened in
+    /// the last replayed block.
+    pub async fn verify(&self, history: &BalanceHistory) -> Result<Vec<Discrepancy>, Error> {
+        let mut discrepancies = Vec::new();
+        let mut blocks = history
+            .snapshots
+            .iter()
+            .map(|snapshot| snapshot.block_number)
+            .collect::<Vec<_>>();
+
+        if blocks.last() != Some(history.blocks.end()) && !history.blocks.is_empty() {
+            blocks.push(*history.blocks.end());
+        }
+
+        for block in blocks {
+            for holder in &self.holders {
+                let Some(expected) = history.balance_at(*holder, block) else {
+                    continue;
+                };
+                let actual = self.balance_of(*holder, block).await?;
+
+                if expected != actual {
+                    discrepancies.push(Discrepancy {
+                        holder: *holder,
+                        block_number: block,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        Ok(discrepancies)
+    }
+
+    async fn balance_of(&self, holder: Address, block: u64) -> Result<U256, Error> {
+        self.token
+            .balance_of_at(holder, BlockId::number(block))
+            .await
+    }
+
+    fn error(&self, err: impl Into<crate::InternalError>) -> Error {
+        Error::new(TokenId::from(*self.token.address()), err)
+    }
+}
+
+/// Applies `transfers`, ordered by block, to the `initial` balances,
+/// returning a snapshot for every block changing an indexed balance, and the
+/// transfers debiting more than the balance of a holder.
+///
+/// The balance of such a holder is replaced by its `synced` balance after
+/// the block, ignoring its other transfers in the block. Fails with the
+/// block and holder if that balance is missing.
+fn replay(
+    initial: &HashMap<Address, U256>,
+    transfers: &[(u64, Address, Address, U256)],
+    synced: &HashMap<(u64, Address), U256>,
+) -> Result<(Vec<BalanceSnapshot>, Vec<Discrepancy>), (u64, Address)> {
+    let mut balances = initial.clone();
+    let mut snapshots: Vec<BalanceSnapshot> = Vec::new();
+    let mut discrepancies: Vec<Discrepancy> = Vec::new();
+
+    let is_synced = |discrepancies: &[Discrepancy], block: u64, holder: Address| {
+        discrepancies
+            .iter()
+            .rev()
+            .take_while(|discrepancy| discrepancy.block_number == block)
+            .any(|discrepancy| discrepancy.holder == holder)
+    };
+
+    for &(block, from, to, value) in transfers {
+        if !is_synced(&discrepancies, block, from) {
+            if let Some(balance) = balances.get_mut(&from) {
+                match balance.checked_sub(value) {
+                    Some(remaining) => *balance = remaining,
+                    None => {
+                        let actual = *synced.get(&(block, from)).ok_or((block, from))?;
+                        discrepancies.push(Discrepancy {
+                            holder: from,
+                            block_number: block,
+                            expected: *balance,
+                            actual,
+                        });
+                        *balance = actual;
+                    }
+                }
+            }
+        }
+
+        if !is_synced(&discrepancies, block, to) {
+            if let Some(balance) = balances.get_mut(&to) {
+                *balance = balance.saturating_add(value);
+            }
+        }
+
+        match snapshots.last_mut() {
+            Some(snapshot) if snapshot.block_number == block => {
+                snapshot.balances.clone_from(&balances);
+            }
+            _ => snapshots.push(BalanceSnapshot {
+                block_number: block,
+                balances: balances.clone(),
+            }),
+        }
+    }
+
+    Ok((snapshots, discrepancies))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use alloy::primitives::{address, Address, U256};
+
+    use super::{replay, BalanceHistory, Discrepancy};
+
+    const ALICE: Address = address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+    const BOB: Address = address!("70997970C51812dc3A010C7d01b50e0d17dc79C8");
+    const CAROL: Address = address!("3C44CdDdB6a900fa2b585dd299e03d12FA4293BC");
+
+    #[test]
+    fn test_replay() {
+        let initial = HashMap::from([(ALICE, U256::from(100)), (BOB, U256::ZERO)]);
+
+        let (snapshots, discrepancies) = replay(
+            &initial,
+            &[
+                (10, ALICE, BOB, U256::from(30)),
+                (10, BOB, CAROL, U256::from(10)),
+                (12, Address::ZERO, ALICE, U256::from(5)),
+            ],
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].block_number, 10);
+        assert_eq!(snapshots[0].balances[&ALICE], U256::from(70));
+        assert_eq!(snapshots[0].balances[&BOB], U256::from(20));
+        assert!(!snapshots[0].balances.contains_key(&CAROL));
+        assert_eq!(snapshots[1].block_number, 12);
+        assert_eq!(snapshots[1].balances[&ALICE], U256::from(75));
+        assert!(discrepancies.is_empty());
+    }
+
+    #[test]
+    fn test_replay_underflow() {
+        let initial = HashMap::from([(ALICE, U256::from(10)), (BOB, U256::ZERO)]);
+        let transfers = [
+            (10, ALICE, BOB, U256::from(30)),
+            (10, Address::ZERO, ALICE, U256::from(5)),
+            (12, ALICE, BOB, U256::from(20)),
+        ];
+
+        assert_eq!(
+            replay(&initial, &transfers, &HashMap::new()).unwrap_err(),
+            (10, ALICE)
+        );
+
+        let synced = HashMap::from([((10, ALICE), U256::from(25))]);
+        let (snapshots, discrepancies) = replay(&initial, &transfers, &synced).unwrap();
+
+        assert_eq!(
+            discrepancies,
+            [Discrepancy {
+                holder: ALICE,
+                block_number: 10,
+                expected: U256::from(10),
+                actual: U256::from(25),
+            }]
+        );
+        assert_eq!(snapshots[0].balances[&ALICE], U256::from(25));
+        assert_eq!(snapshots[0].balances[&BOB], U256::from(30));
+        assert_eq!(snapshots[1].balances[&ALICE], U256::from(5));
+        assert_eq!(snapshots[1].balances[&BOB], U256::from(50));
+    }
+
+    #[test]
+    fn test_balance_at() {
+        let initial = HashMap::from([(ALICE, U256::from(100))]);
+        let history = BalanceHistory {
+            blocks: 10..=20,
+            snapshots: replay(
+                &initial,
+                &[
+                    (12, ALICE, BOB, U256::from(30)),
+                    (15, ALICE, BOB, U256::from(20)),
+                ],
+                &HashMap::new(),
+            )
+            .unwrap()
+            .0,
+            initial,
+            discrepancies: Vec::new(),
+        };
+
+        assert_eq!(history.balance_at(ALICE, 9), None);
+        assert_eq!(history.balance_at(ALICE, 11), Some(U256::from(100)));
+        assert_eq!(history.balance_at(ALICE, 12), Some(U256::from(70)));
+        assert_eq!(history.balance_at(ALICE, 14), Some(U256::from(70)));
+        assert_eq!(history.balance_at(ALICE, 20), Some(U256::from(50)));
+        assert_eq!(history.balance_at(ALICE, 21), None);
+        assert_eq!(history.balance_at(BOB, 15), None);
+    }
+}
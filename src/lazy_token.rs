@@ -1,9 +1,14 @@
-use crate::provider::Erc20Contract;
+use crate::{
+    error::InternalError, native::native_token, provider::Erc20Contract, DecodedString, Error,
+    StringEncoding, Token, TokenId, NATIVE_ADDRESS,
+};
 use alloy::{
-    contract::Error,
-    network::Network,
+    contract::SolCallBuilder,
+    eips::BlockId,
+    network::{Network, ReceiptResponse, TransactionBuilder},
     primitives::{Address, U256},
-    providers::Provider,
+    providers::{PendingTransactionBuilder, Provider},
+    sol_types::{sol_data, SolCall, SolType},
 };
 use async_once_cell::OnceCell;
 use bigdecimal::{
@@ -20,10 +25,10 @@ use std::{
 /// A token with an embedded contract instance that lazily query the
 /// blockchain.
 pub struct LazyToken<P, N> {
-    name: OnceCell<String>,
-    symbol: OnceCell<String>,
+    name: OnceCell<DecodedString>,
+    symbol: OnceCell<DecodedString>,
     decimals: OnceCell<u8>,
-    instance: Erc20Contract::Erc20ContractInstance<P, N>,
+    pub(crate) instance: Erc20Contract::Erc20ContractInstance<P, N>,
 }
 
 impl<P, N> LazyToken<P, N>
@@ -46,73 +51,190 @@ where
         self.instance.address()
     }
 
+    /// Returns the provider used to query the token.
+    pub const fn provider(&self) -> &P {
+        self.instance.provider()
+    }
+
+    /// Returns `true` if the token is the native asset of the chain, see
+    /// [`NATIVE_ADDRESS`].
+    pub fn is_native(&self) -> bool {
+        *self.address() == NATIVE_ADDRESS
+    }
+
+    async fn native_token(&self) -> Result<Token, Error> {
+        let chain_id = self
+            .provider()
+            .get_chain_id()
+            .await
+            .map_err(|err| self.error(err))?;
+
+        Ok(native_token(chain_id))
+    }
+
+    /// Fails with [`InternalError::NativeAsset`] if the token is the native
+    /// asset, which has no `function`.
+    pub(crate) fn ensure_erc20(&self, function: &'static str) -> Result<(), Error> {
+        if self.is_native() {
+            Err(self.error(InternalError::NativeAsset(function)))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Wraps an error from a token call.
+    pub(crate) fn error(&self, err: impl Into<InternalError>) -> Error {
+        Error::new((*self.address()).into(), err)
+    }
+
     /// Returns the name of the token.
     pub async fn name(&self) -> Result<&String, Error> {
+        self.name_decoded().await.map(|name| &name.value)
+    }
+
+    /// Returns the name of the token, along with the encoding it was
+    /// returned with.
+    ///
+    /// See [`DecodedString::decode`] for the supported encodings.
+    pub async fn name_decoded(&self) -> Result<&DecodedString, Error> {
         self.name
-            .get_or_try_init(
-                self.instance
+            .get_or_try_init(async {
+                if self.is_native() {
+                    let name = self.native_token().await?.name.unwrap_or_default();
+                    return Ok(native_string(name));
+                }
+
+                let data = self
+                    .instance
                     .name()
-                    .call()
-                    .into_future()
-                    .and_then(|r| ready(Ok(r))),
-            )
+                    .call_raw()
+                    .await
+                    .map_err(|err| self.error(err))?;
+                DecodedString::decode(&data).map_err(|err| self.error(err))
+            })
             .await
     }
 
     /// Returns the symbol of the token.
     pub async fn symbol(&self) -> Result<&String, Error> {
+        self.symbol_decoded().await.map(|symbol| &symbol.value)
+    }
+
+    /// Returns the symbol of the token, along with the encoding it was
+    /// returned with.
+    ///
+    /// See [`DecodedString::decode`] for the supported encodings.
+    pub async fn symbol_decoded(&self) -> Result<&DecodedString, Error> {
         self.symbol
-            .get_or_try_init(
-                self.instance
+            .get_or_try_init(async {
+                if self.is_native() {
+                    return Ok(native_string(self.native_token().await?.symbol));
+                }
+
+                let data = self
+                    .instance
                     .symbol()
-                    .call()
-                    .into_future()
-                    .and_then(|r| ready(Ok(r))),
-            )
+                    .call_raw()
+                    .await
+                    .map_err(|err| self.error(err))?;
+                DecodedString::decode(&data).map_err(|err| self.error(err))
+            })
             .await
     }
 
     /// Returns the decimals places of the token.
     pub async fn decimals(&self) -> Result<&u8, Error> {
         self.decimals
-            .get_or_try_init(
+            .get_or_try_init(async {
+                if self.is_native() {
+                    return Ok(self.native_token().await?.decimals);
+                }
+
                 self.instance
                     .decimals()
                     .call()
-                    .into_future()
-                    .and_then(|r| ready(Ok(r))),
-            )
+                    .await
+                    .map_err(|err| self.error(err))
+            })
             .await
     }
 
     /// Returns the amount of tokens in existence.
     pub async fn total_supply(&self) -> Result<U256, Error> {
+        self.total_supply_at(BlockId::latest()).await
+    }
+
+    /// Returns the amount of tokens in existence at `block`.
+    ///
+    /// The supply of the native asset isn't available, failing with
+    /// [`InternalError::NativeAsset`].
+    pub async fn total_supply_at(&self, block: BlockId) -> Result<U256, Error> {
+        self.ensure_erc20("totalSupply")?;
+
         self.instance
             .totalSupply()
+            .block(block)
             .call()
             .into_future()
             .and_then(|r| ready(Ok(r)))
+            .map_err(|err| self.error(err))
             .await
     }
 
     /// Returns the value of tokens owned by `account`.
     pub async fn balance_of(&self, account: Address) -> Result<U256, Error> {
+        self.balance_of_at(account, BlockId::latest()).await
+    }
+
+    /// Returns the value of tokens owned by `account` at `block`.
+    ///
+    /// The balance of the native asset is read with `eth_getBalance`.
+    pub async fn balance_of_at(&self, account: Address, block: BlockId) -> Result<U256, Error> {
+        if self.is_native() {
+            return self
+                .provider()
+                .get_balance(account)
+                .block_id(block)
+                .await
+                .map_err(|err| self.error(err));
+        }
+
         self.instance
             .balanceOf(account)
+            .block(block)
             .call()
             .into_future()
             .and_then(|r| ready(Ok(r)))
+            .map_err(|err| self.error(err))
             .await
     }
 
     /// Returns the remaining number of tokens that `spender` will be
     /// allowed to spend on behalf of `owner`.
     pub async fn allowance(&self, owner: Address, spender: Address) -> Result<U256, Error> {
+        self.allowance_at(owner, spender, BlockId::latest()).await
+    }
+
+    /// Returns the remaining number of tokens that `spender` was allowed to
+    /// spend on behalf of `owner` at `block`.
+    ///
+    /// The native asset has no allowances, failing with
+    /// [`InternalError::NativeAsset`].
+    pub async fn allowance_at(
+        &self,
+        owner: Address,
+        spender: Address,
+        block: BlockId,
+    ) -> Result<U256, Error> {
+        self.ensure_erc20("allowance")?;
+
         self.instance
             .allowance(owner, spender)
+            .block(block)
             .call()
             .into_future()
             .and_then(|r| ready(Ok(r)))
+            .map_err(|err| self.error(err))
             .await
     }
 
@@ -174,8 +296,8 @@ where
     P: Provider<N>,
     N: Network,
 {
-    token: LazyToken<P, N>,
-    instance: Erc20Contract::Erc20ContractInstance<P, N>,
+    pub(crate) token: LazyToken<P, N>,
+    pub(crate) instance: Erc20Contract::Erc20ContractInstance<P, N>,
 }
 
 impl<P, N> LazyTokenSigner<P, N>
@@ -201,16 +323,39 @@ where
         self.token.address()
     }
 
+    /// Returns the provider used to query the token and send transactions.
+    pub const fn provider(&self) -> &P {
+        self.token.provider()
+    }
+
+    /// Returns `true` if the token is the native asset of the chain, see
+    /// [`NATIVE_ADDRESS`].
+    pub fn is_native(&self) -> bool {
+        self.token.is_native()
+    }
+
     /// Returns the name of the token.
     pub async fn name(&self) -> Result<&String, Error> {
         self.token.name().await
     }
 
+    /// Returns the name of the token, along with the encoding it was
+    /// returned with.
+    pub async fn name_decoded(&self) -> Result<&DecodedString, Error> {
+        self.token.name_decoded().await
+    }
+
     /// Returns the symbol of the token.
     pub async fn symbol(&self) -> Result<&String, Error> {
         self.token.symbol().await
     }
 
+    /// Returns the symbol of the token, along with the encoding it was
+    /// returned with.
+    pub async fn symbol_decoded(&self) -> Result<&DecodedString, Error> {
+        self.token.symbol_decoded().await
+    }
+
     /// Returns the decimals places of the token.
     pub async fn decimals(&self) -> Result<&u8, Error> {
         self.token.decimals().await
@@ -221,17 +366,38 @@ where
         self.token.total_supply().await
     }
 
+    /// Returns the amount of tokens in existence at `block`.
+    pub async fn total_supply_at(&self, block: BlockId) -> Result<U256, Error> {
+        self.token.total_supply_at(block).await
+    }
+
     /// Returns the value of tokens owned by `account`.
     pub async fn balance_of(&self, account: Address) -> Result<U256, Error> {
         self.token.balance_of(account).await
     }
 
+    /// Returns the value of tokens owned by `account` at `block`.
+    pub async fn balance_of_at(&self, account: Address, block: BlockId) -> Result<U256, Error> {
+        self.token.balance_of_at(account, block).await
+    }
+
     /// Returns the remaining number of tokens that `spender` will be
     /// allowed to spend on behalf of `owner`.
     pub async fn allowance(&self, owner: Address, spender: Address) -> Result<U256, Error> {
         self.token.allowance(owner, spender).await
     }
 
+    /// Returns the remaining number of tokens that `spender` was allowed to
+    /// spend on behalf of `owner` at `block`.
+    pub async fn allowance_at(
+        &self,
+        owner: Address,
+        spender: Address,
+        block: BlockId,
+    ) -> Result<U256, Error> {
+        self.token.allowance_at(owner, spender, block).await
+    }
+
     /// Gets the token balance as a [`BigDecimal`]
     pub async fn get_balance(&self, amount: U256) -> Result<BigDecimal, Error> {
         self.token.get_balance(amount).await
@@ -239,6 +405,10 @@ where
 
     /// Transfers `amount` tokens to `to`.
     ///
+    /// `amount` is in base units of the token, see
+    /// [`LazyTokenSigner::transfer_amount`] to transfer a
+    /// [`TokenAmount`](crate::TokenAmount).
+    ///
     /// This sends the transaction to the network. Use the returned
     /// pending transaction builder to wait for confirmation:
     ///
@@ -261,28 +431,49 @@ where
     ///
     /// Returns an error if the transaction fails to send or if there's
     /// insufficient balance/gas.
+    ///
+    /// The native asset is transferred with a plain value transfer.
     pub async fn transfer(
         &self,
         to: Address,
         amount: U256,
-    ) -> Result<alloy::providers::PendingTransactionBuilder<N>, Error> {
-        self.instance.transfer(to, amount).send().await
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        if self.is_native() {
+            return self.send_value(to, amount).await;
+        }
+
+        self.instance
+            .transfer(to, amount)
+            .send()
+            .await
+            .map_err(|err| self.token.error(err))
     }
 
     /// Approves `spender` to transfer up to `amount` tokens on behalf of the caller.
     ///
+    /// `amount` is in base units of the token, see
+    /// [`LazyTokenSigner::approve_amount`] to approve a
+    /// [`TokenAmount`](crate::TokenAmount).
+    ///
     /// This sends the transaction to the network. Use the returned
     /// pending transaction builder to wait for confirmation.
     ///
     /// # Errors
     ///
-    /// Returns an error if the transaction fails to send.
+    /// Returns an error if the transaction fails to send, or if the token
+    /// is the native asset, which has no allowances.
     pub async fn approve(
         &self,
         spender: Address,
         amount: U256,
-    ) -> Result<alloy::providers::PendingTransactionBuilder<N>, Error> {
-        self.instance.approve(spender, amount).send().await
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.token.ensure_erc20("approve")?;
+
+        self.instance
+            .approve(spender, amount)
+            .send()
+            .await
+            .map_err(|err| self.token.error(err))
     }
 
     /// Transfers `amount` tokens from `from` to `to` using the allowance mechanism.
@@ -293,14 +484,240 @@ where
     ///
     /// # Errors
     ///
-    /// Returns an error if the transaction fails to send or if there's
-    /// insufficient allowance.
+    /// Returns an error if the transaction fails to send, if there's
+    /// insufficient allowance, or if the token is the native asset.
     pub async fn transfer_from(
         &self,
         from: Address,
         to: Address,
         amount: U256,
-    ) -> Result<alloy::providers::PendingTransactionBuilder<N>, Error> {
-        self.instance.transferFrom(from, to, amount).send().await
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.token.ensure_erc20("transferFrom")?;
+
+        self.instance
+            .transferFrom(from, to, amount)
+            .send()
+            .await
+            .map_err(|err| self.token.error(err))
+    }
+
+    /// Transfers `amount` tokens to `to`, with [SafeERC20] semantics.
+    ///
+    /// The transfer is first simulated with an `eth_call` to check its
+    /// return value: tokens returning nothing (like USDT) are considered
+    /// successful, unless the address has no code, failing with
+    /// [`InternalError::NoCode`], while tokens returning `false` fail with
+    /// [`InternalError::ReturnedFalse`]. The transaction is then sent, and
+    /// only its receipt status is checked once included: a receipt doesn't
+    /// carry the return value, so a token returning `false` on chain after
+    /// a successful simulation isn't detected.
+    ///
+    /// [SafeERC20]: https://docs.openzeppelin.com/contracts/5.x/api/token/erc20#SafeERC20
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transfer reverts or returns `false` when
+    /// simulated, or if the transaction reverts once included.
+    pub async fn safe_transfer(
+        &self,
+        to: Address,
+        amount: U256,
+    ) -> Result<N::ReceiptResponse, Error> {
+        if self.is_native() {
+            let pending = self.send_value(to, amount).await?;
+
+            return self.confirm(pending).await;
+        }
+
+        self.send_checked(self.instance.transfer(to, amount)).await
+    }
+
+    /// Approves `spender` to transfer up to `amount` tokens on behalf of the
+    /// caller, with [SafeERC20] semantics.
+    ///
+    /// See [`LazyTokenSigner::safe_transfer`] for how the outcome is checked.
+    ///
+    /// [SafeERC20]: https://docs.openzeppelin.com/contracts/5.x/api/token/erc20#SafeERC20
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the approval reverts or returns `false` when
+    /// simulated, or if the transaction reverts once included.
+    pub async fn safe_approve(
+        &self,
+        spender: Address,
+        amount: U256,
+    ) -> Result<N::ReceiptResponse, Error> {
+        self.token.ensure_erc20("approve")?;
+
+        self.send_checked(self.instance.approve(spender, amount))
+            .await
+    }
+
+    /// Transfers `amount` tokens from `from` to `to` using the allowance
+    /// mechanism, with [SafeERC20] semantics.
+    ///
+    /// See [`LazyTokenSigner::safe_transfer`] for how the outcome is checked.
+    ///
+    /// [SafeERC20]: https://docs.openzeppelin.com/contracts/5.x/api/token/erc20#SafeERC20
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transfer reverts or returns `false` when
+    /// simulated, or if the transaction reverts once included.
+    pub async fn safe_transfer_from(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<N::ReceiptResponse, Error> {
+        self.token.ensure_erc20("transferFrom")?;
+
+        self.send_checked(self.instance.transferFrom(from, to, amount))
+            .await
+    }
+
+    /// Simulates `call` to check its optional `bool` return value, then
+    /// sends it and waits for a successful receipt.
+    ///
+    /// Empty return data is only a success if the token has code, calls to
+    /// an address without code always succeeding.
+    async fn send_checked<C: SolCall>(
+        &self,
+        call: SolCallBuilder<&P, C, N>,
+    ) -> Result<N::ReceiptResponse, Error> {
+        let token = TokenId::from(*self.address());
+
+        let output = call
+            .call_raw()
+            .await
+            .map_err(|err| Error::new(token.clone(), err))?;
+
+        check_returned_bool(&output).map_err(|err| Error::new(token.clone(), err))?;
+
+        if output.is_empty() {
+            let code = self
+                .provider()
+                .get_code_at(*self.address())
+                .await
+                .map_err(|err| Error::new(token.clone(), err))?;
+
+            if code.is_empty() {
+                return Err(Error::new(token, InternalError::NoCode));
+            }
+        }
+
+        let pending = call
+            .send()
+            .await
+            .map_err(|err| Error::new(token.clone(), err))?;
+
+        self.confirm(pending).await
+    }
+
+    /// Sends a plain value transfer of `amount` to `to`.
+    async fn send_value(
+        &self,
+        to: Address,
+        amount: U256,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        let tx = N::TransactionRequest::default()
+            .with_to(to)
+            .with_value(amount);
+
+        self.provider()
+            .send_transaction(tx)
+            .await
+            .map_err(|err| self.token.error(err))
+    }
+
+    /// Waits for a successful receipt of `pending`.
+    async fn confirm(
+        &self,
+        pending: PendingTransactionBuilder<N>,
+    ) -> Result<N::ReceiptResponse, Error> {
+        let token = TokenId::from(*self.address());
+
+        let receipt = pending
+            .get_receipt()
+            .await
+            .map_err(|err| Error::new(token.clone(), err))?;
+
+        if !receipt.status() {
+            return Err(Error::new(
+                token,
+                InternalError::Reverted(receipt.transaction_hash()),
+            ));
+        }
+
+        Ok(receipt)
+    }
+}
+
+/// Checks the return data of a call returning an optional `bool`: empty
+/// data is a success, as for tokens not returning anything.
+fn check_returned_bool(data: &[u8]) -> Result<(), InternalError> {
+    if data.is_empty() || sol_data::Bool::abi_decode(data)? {
+        Ok(())
+    } else {
+        Err(InternalError::ReturnedFalse)
+    }
+}
+
+/// Returns the name or symbol of a native asset, which isn't returned by a
+/// contract.
+const fn native_string(value: String) -> DecodedString {
+    DecodedString {
+        value,
+        encoding: StringEncoding::Native,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{Address, U256},
+        providers::ProviderBuilder,
+        transports::mock::Asserter,
+    };
+
+    use super::{LazyToken, LazyTokenSigner};
+    use crate::{error::InternalError, StringEncoding, NATIVE_ADDRESS};
+
+    #[tokio::test]
+    async fn test_native_metadata() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        asserter.push_success(&U256::from(1));
+
+        let token = LazyToken::new(NATIVE_ADDRESS, provider);
+        let symbol = token.symbol_decoded().await.unwrap();
+
+        assert_eq!(symbol.value, "ETH");
+        assert_eq!(symbol.encoding, StringEncoding::Native);
+    }
+
+    #[tokio::test]
+    async fn test_native_erc20_only_functions() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter);
+        let token = LazyTokenSigner::new(NATIVE_ADDRESS, provider);
+
+        // Rejected before any request is sent to the empty mock
+        let err = token.total_supply().await.unwrap_err();
+        assert!(matches!(
+            err.source,
+            InternalError::NativeAsset("totalSupply")
+        ));
+        assert_eq!(err.token, NATIVE_ADDRESS.into());
+
+        let err = token
+            .transfer_from(Address::ZERO, Address::ZERO, U256::ZERO)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.source,
+            InternalError::NativeAsset("transferFrom")
+        ));
     }
 }
@@ -10,14 +10,42 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+mod amount;
+pub use amount::TokenAmount;
+
+mod authorization;
+pub use authorization::{
+    CancelAuthorization, ReceiveWithAuthorization, SignedAuthorization, TransferWithAuthorization,
+};
+
 mod constants;
 pub use constants::*;
 
+mod erc1363;
+pub use erc1363::{ERC1363_INTERFACE_ID, ERC165_INTERFACE_ID};
+
+mod events;
+pub use events::{ApprovalEvent, TransferEvent, TransferFilter};
+
+mod indexer;
+pub use indexer::{BalanceHistory, BalanceIndexer, BalanceSnapshot, Discrepancy};
+
+mod metadata;
+pub use metadata::{DecodedString, DecodedToken, StringEncoding};
+
+mod multicall;
+
+mod profile;
+pub use profile::{DecimalsStatus, Proxy, TokenProfile, TokenProfileCache};
+
 mod provider;
 pub use provider::Erc20ProviderExt;
 
 mod error;
-pub use error::Error;
+pub use error::{Error, InternalError};
+
+mod resolver;
+pub use resolver::{EnsResolver, SymbolResolver, ENS_REGISTRY_ADDRESS};
 
 mod token;
 pub use token::Token;
@@ -25,11 +53,43 @@ pub use token::Token;
 mod lazy_token;
 pub use lazy_token::{LazyToken, LazyTokenSigner};
 
+mod native;
+pub use native::{native_token, NATIVE_ADDRESS};
+
+mod permit;
+pub use permit::{Permit, SignedPermit};
+
+mod permit2;
+pub use permit2::{
+    Permit2, Permit2Allowance, PermitBatch, PermitDetails, PermitSingle, PermitTransferFrom,
+    SignedPermit2, TokenPermissions, PERMIT2_ADDRESS,
+};
+
+#[cfg(feature = "token-list")]
+mod token_list;
+#[cfg(feature = "token-list")]
+pub use token_list::{TokenInfo, TokenList, TokenListError, Version};
+
+mod units;
+pub use units::{FormatOptions, Rounding};
+
+mod vault;
+pub use vault::{LazyVault, LazyVaultSigner};
+
+mod wrapped;
+pub use wrapped::{wrapped_native, WrappedNative};
+
 mod token_id;
 pub use token_id::TokenId;
 
 mod stores;
-pub use stores::{BasicTokenStore, Entry, StoreIter, TokenStore};
+pub use stores::{
+    BasicTokenStore, ConcurrentTokenStore, ConflictPolicy, Entry, SharedTokenStore, StoreIter,
+    StoreSnapshot, TokenStore,
+};
+
+#[cfg(feature = "file-store")]
+pub use stores::FileTokenStore;
 
 #[cfg(feature = "lru-store")]
 pub use stores::LruTokenStore;
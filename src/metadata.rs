@@ -0,0 +1,136 @@
+use alloy::sol_types::{sol_data, SolType};
+
+use crate::Token;
+
+/// The encoding a token used to return a string metadata, like its `name`
+/// or `symbol`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StringEncoding {
+    /// An ABI-encoded `string`, as specified by ERC-20.
+    String,
+    /// A `bytes32`, padded with trailing NUL bytes, as returned by some
+    /// legacy tokens like MKR or SAI.
+    Bytes32,
+    /// Raw UTF-8 bytes that are neither of the above.
+    Raw,
+    /// Not returned by a contract, as for the native asset of a chain.
+    Native,
+}
+
+/// A token string metadata, along with the encoding it was returned with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecodedString {
+    /// The decoded value.
+    pub value: String,
+    /// The encoding the value was returned with.
+    pub encoding: StringEncoding,
+}
+
+/// A token retrieved from its contract, along with the encodings its string
+/// metadata were returned with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedToken {
+    /// The token.
+    pub token: Token,
+    /// The encoding the symbol was returned with,
+    /// [`StringEncoding::Native`] for the native asset.
+    pub symbol_encoding: StringEncoding,
+    /// The encoding the name was returned with, `None` if it wasn't
+    /// retrieved.
+    pub name_encoding: Option<StringEncoding>,
+}
+
+impl DecodedToken {
+    /// Wraps the native asset of a chain, whose metadata aren't returned
+    /// by a contract.
+    pub(crate) fn native(token: Token) -> Self {
+        let name_encoding = token.name.as_ref().map(|_| StringEncoding::Native);
+
+        Self {
+            token,
+            symbol_encoding: StringEncoding::Native,
+            name_encoding,
+        }
+    }
+}
+
+impl DecodedString {
+    /// Decodes the return data of a `name` or `symbol` call.
+    ///
+    /// The data is tried as a `string` first, then as a `bytes32`, and
+    /// finally as raw UTF-8 bytes. Trailing NUL bytes are trimmed from the
+    /// two latter.
+    pub fn decode(data: &[u8]) -> Result<Self, alloy::sol_types::Error> {
+        let err = match sol_data::String::abi_decode(data) {
+            Ok(value) => return Ok(Self::new(value, StringEncoding::String)),
+            Err(err) => err,
+        };
+
+        let trimmed = match data.iter().rposition(|b| *b != 0) {
+            Some(end) => &data[..=end],
+            None => return Err(err),
+        };
+
+        let encoding = if data.len() == 32 {
+            StringEncoding::Bytes32
+        } else {
+            StringEncoding::Raw
+        };
+
+        match std::str::from_utf8(trimmed) {
+            Ok(value) => Ok(Self::new(value.to_string(), encoding)),
+            Err(_) => Err(err),
+        }
+    }
+
+    const fn new(value: String, encoding: StringEncoding) -> Self {
+        Self { value, encoding }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{b256, bytes},
+        sol_types::{sol_data, SolType},
+    };
+
+    use super::{DecodedString, StringEncoding};
+
+    #[test]
+    fn test_decode_string() {
+        let data = sol_data::String::abi_encode("DAI");
+        let decoded = DecodedString::decode(&data).unwrap();
+
+        assert_eq!(decoded.value, "DAI");
+        assert_eq!(decoded.encoding, StringEncoding::String);
+    }
+
+    #[test]
+    fn test_decode_bytes32() {
+        // MKR symbol
+        let data = b256!("4d4b520000000000000000000000000000000000000000000000000000000000");
+        let decoded = DecodedString::decode(data.as_slice()).unwrap();
+
+        assert_eq!(decoded.value, "MKR");
+        assert_eq!(decoded.encoding, StringEncoding::Bytes32);
+    }
+
+    #[test]
+    fn test_decode_raw() {
+        let decoded = DecodedString::decode(b"SAI\0").unwrap();
+
+        assert_eq!(decoded.value, "SAI");
+        assert_eq!(decoded.encoding, StringEncoding::Raw);
+    }
+
+    #[test]
+    fn test_decode_empty() {
+        assert!(DecodedString::decode(&[]).is_err());
+    }
+
+    #[test]
+    fn test_decode_invalid_utf8() {
+        assert!(DecodedString::decode(&bytes!("ff00")).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use alloy::{
+    eips::BlockId,
+    network::Network,
+    primitives::{Address, Bytes},
+    providers::{Provider, MULTICALL3_ADDRESS},
+    sol,
+    sol_types::SolCall,
+};
+
+use crate::{error::InternalError, DecodedString};
+
+sol! {
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData);
+
+        function getEthBalance(address addr) external view returns (uint256 balance);
+    }
+}
+
+/// A set of heterogeneous calls executed in a single Multicall3 `aggregate3`
+/// round-trip, each call being allowed to fail independently.
+#[derive(Debug, Default)]
+pub(crate) struct Batch {
+    calls: Vec<IMulticall3::Call3>,
+}
+
+impl Batch {
+    /// Queues `call` against `target`.
+    pub(crate) fn push<C: SolCall>(&mut self, target: Address, call: C) {
+        self.calls.push(IMulticall3::Call3 {
+            target,
+            allowFailure: true,
+            callData: call.abi_encode().into(),
+        });
+    }
+
+    /// Queues raw `call_data` against `target`, like calldata built from a
+    /// contract instance.
+    pub(crate) fn push_raw(&mut self, target: Address, call_data: Bytes) {
+        self.calls.push(IMulticall3::Call3 {
+            target,
+            allowFailure: true,
+            callData: call_data,
+        });
+    }
+
+    /// Executes the queued calls, returning their raw results in order.
+    pub(crate) async fn call<P, N>(
+        self,
+        provider: &P,
+    ) -> Result<Vec<IMulticall3::Result>, alloy::contract::Error>
+    where
+        P: Provider<N>,
+        N: Network,
+    {
+        self.call_at(provider, BlockId::latest()).await
+    }
+
+    /// Executes the queued calls at `block`, returning their raw results in
+    /// order.
+    ///
+    /// Fails if Multicall3 doesn't return one result per call, like a
+    /// non-canonical contract deployed at its address.
+    pub(crate) async fn call_at<P, N>(
+        self,
+        provider: &P,
+        block: BlockId,
+    ) -> Result<Vec<IMulticall3::Result>, alloy::contract::Error>
+    where
+        P: Provider<N>,
+        N: Network,
+    {
+        if self.calls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let calls = self.calls.len();
+        let results = IMulticall3::new(MULTICALL3_ADDRESS, provider)
+            .aggregate3(self.calls)
+            .block(block)
+            .call()
+            .await?;
+
+        if results.len() != calls {
+            return Err(alloy::sol_types::Error::custom(format!(
+                "expected {calls} Multicall3 results, got {}",
+                results.len()
+            ))
+            .into());
+        }
+
+        Ok(results)
+    }
+}
+
+/// Decodes the return data of a single call from a [`Batch`].
+pub(crate) fn decode<C: SolCall>(result: &IMulticall3::Result) -> Result<C::Return, InternalError> {
+    Ok(C::abi_decode_returns(return_data(result)?)?)
+}
+
+/// Decodes the return data of a `name` or `symbol` call from a [`Batch`],
+/// tolerating non-standard encodings.
+pub(crate) fn decode_string(result: &IMulticall3::Result) -> Result<DecodedString, InternalError> {
+    Ok(DecodedString::decode(return_data(result)?)?)
+}
+
+/// Returns the raw return data of a single call from a [`Batch`], failing if
+/// the call reverted.
+pub(crate) fn return_data(result: &IMulticall3::Result) -> Result<&[u8], InternalError> {
+    if !result.success {
+        return Err(InternalError::CallFailed(result.returnData.clone()));
+    }
+
+    Ok(&result.returnData)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{Address, Bytes},
+        providers::ProviderBuilder,
+        sol_types::SolCall,
+        transports::mock::Asserter,
+    };
+
+    use super::{Batch, IMulticall3};
+
+    #[tokio::test]
+    async fn test_missing_results() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+
+        let result = IMulticall3::Result {
+            success: true,
+            returnData: Bytes::new(),
+        };
+        let output = IMulticall3::aggregate3Call::abi_encode_returns(&vec![result]);
+        asserter.push_success(&Bytes::from(output));
+
+        let mut batch = Batch::default();
+        batch.push(
+            Address::ZERO,
+            IMulticall3::getEthBalanceCall {
+                addr: Address::ZERO,
+            },
+        );
+        batch.push(
+            Address::ZERO,
+            IMulticall3::getEthBalanceCall {
+                addr: Address::ZERO,
+            },
+        );
+
+        let err = batch.call(&provider).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected 2 Multicall3 results, got 1"));
+    }
+}
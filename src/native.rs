@@ -0,0 +1,54 @@
+use alloy::primitives::{address, Address};
+
+use crate::Token;
+
+/// The pseudo-address of the native asset of a chain, like ETH on Ethereum,
+/// as used by most aggregators and wallets.
+///
+/// Tokens at this address are handled as the native asset: balances are
+/// read with `eth_getBalance`, and transfers are plain value transfers.
+pub const NATIVE_ADDRESS: Address = address!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+
+/// Returns the native asset of a chain, as a token at [`NATIVE_ADDRESS`].
+///
+/// Chains with an unknown native asset default to Ether, all EVM native
+/// assets having 18 decimals.
+pub fn native_token(chain_id: u64) -> Token {
+    let (symbol, name) = match chain_id {
+        56 | 97 => ("BNB", "BNB"),
+        100 => ("XDAI", "xDAI"),
+        137 | 80002 => ("POL", "Polygon Ecosystem Token"),
+        43113 | 43114 => ("AVAX", "Avalanche"),
+        _ => ("ETH", "Ether"),
+    };
+
+    Token::new(NATIVE_ADDRESS, symbol.to_string(), 18).with_name(name.to_string())
+}
+
+impl Token {
+    /// Returns `true` if the token is the native asset of its chain, see
+    /// [`NATIVE_ADDRESS`].
+    pub fn is_native(&self) -> bool {
+        self.address == NATIVE_ADDRESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{native_token, NATIVE_ADDRESS};
+    use crate::mainnet;
+
+    #[test]
+    fn test_native_token() {
+        assert_eq!(native_token(1).symbol, mainnet::ETH.symbol);
+        assert_eq!(mainnet::ETH.address, NATIVE_ADDRESS);
+        assert!(mainnet::ETH.is_native());
+        assert!(!mainnet::WETH.is_native());
+
+        let pol = native_token(137);
+        assert_eq!(pol.symbol, "POL");
+        assert_eq!(pol.decimals, 18);
+        assert_eq!(native_token(43114).symbol, "AVAX");
+        assert_eq!(native_token(8453).symbol, "ETH");
+    }
+}
@@ -0,0 +1,284 @@
+use alloy::{
+    network::Network,
+    primitives::{Address, Signature, B256, U256},
+    providers::{PendingTransactionBuilder, Provider},
+    signers::Signer,
+    sol,
+    sol_types::{Eip712Domain, SolStruct},
+};
+
+use crate::{error::InternalError, Error, LazyToken, LazyTokenSigner, TokenId};
+
+sol! {
+    #[sol(rpc)]
+    #[derive(Debug)]
+    #[allow(clippy::too_many_arguments)]
+    interface IERC20Permit {
+        function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+        function nonces(address owner) external view returns (uint256);
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+    }
+
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IERC5267 {
+        function eip712Domain() external view returns (bytes1 fields, string name, string version, uint256 chainId, address verifyingContract, bytes32 salt, uint256[] extensions);
+    }
+
+    /// The [EIP-2612] `Permit` typed data.
+    ///
+    /// [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+    #[derive(Debug, PartialEq, Eq)]
+    struct Permit {
+        address owner;
+        address spender;
+        uint256 value;
+        uint256 nonce;
+        uint256 deadline;
+    }
+}
+
+/// The domain versions tried, in order, when a token doesn't implement
+/// [ERC-5267](https://eips.ethereum.org/EIPS/eip-5267).
+const DOMAIN_VERSIONS: [&str; 2] = ["1", "2"];
+
+/// A signed [EIP-2612] permit, ready to be submitted or relayed.
+///
+/// [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedPermit {
+    /// The permit.
+    pub permit: Permit,
+    /// The permit signature, from its owner.
+    pub signature: Signature,
+}
+
+impl<P, N> LazyToken<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Returns the current [EIP-2612] nonce of `owner`.
+    ///
+    /// [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+    pub async fn nonces(&self, owner: Address) -> Result<U256, Error> {
+        IERC20Permit::new(*self.address(), self.provider())
+            .nonces(owner)
+            .call()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))
+    }
+
+    /// Returns the [EIP-712] domain separator of the token.
+    ///
+    /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+    pub async fn domain_separator(&self) -> Result<B256, Error> {
+        IERC20Permit::new(*self.address(), self.provider())
+            .DOMAIN_SEPARATOR()
+            .call()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))
+    }
+
+    /// Returns the [EIP-712] domain of the token.
+    ///
+    /// The domain is read from [ERC-5267] `eip712Domain()` if the token
+    /// implements it. Otherwise, it is built from the token name and the
+    /// chain id, trying the usual `"1"` and `"2"` versions against the
+    /// token domain separator.
+    ///
+    /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+    /// [ERC-5267]: https://eips.ethereum.org/EIPS/eip-5267
+    pub async fn eip712_domain(&self) -> Result<Eip712Domain, Error> {
+        let token = TokenId::from(*self.address());
+
+        if let Ok(domain) = IERC5267::new(*self.address(), self.provider())
+            .eip712Domain()
+            .call()
+            .await
+        {
+            return Ok(domain_from_erc5267(domain));
+        }
+
+        let separator = self.domain_separator().await?;
+        let name = self.name().await?;
+        let chain_id = self
+            .provider()
+            .get_chain_id()
+            .await
+            .map_err(|err| Error::new(token.clone(), err))?;
+
+        DOMAIN_VERSIONS
+            .iter()
+            .map(|version| {
+                Eip712Domain::new(
+                    Some(name.clone().into()),
+                    Some((*version).into()),
+                    Some(U256::from(chain_id)),
+                    Some(*self.address()),
+                    None,
+                )
+            })
+            .find(|domain| domain.separator() == separator)
+            .ok_or_else(|| Error::new(token, InternalError::UnknownDomain(separator)))
+    }
+}
+
+impl<P, N> LazyTokenSigner<P, N>
+where
+    P: Provider<N> + Clone,
+    N: Network,
+{
+    /// Returns the current [EIP-2612] nonce of `owner`.
+    ///
+    /// [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+    pub async fn nonces(&self, owner: Address) -> Result<U256, Error> {
+        self.token.nonces(owner).await
+    }
+
+    /// Returns the [EIP-712] domain separator of the token.
+    ///
+    /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+    pub async fn domain_separator(&self) -> Result<B256, Error> {
+        self.token.domain_separator().await
+    }
+
+    /// Returns the [EIP-712] domain of the token.
+    ///
+    /// See [`LazyToken::eip712_domain`] for how the domain is resolved.
+    ///
+    /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+    pub async fn eip712_domain(&self) -> Result<Eip712Domain, Error> {
+        self.token.eip712_domain().await
+    }
+
+    /// Signs an [EIP-2612] permit allowing `spender` to transfer up to
+    /// `value` tokens on behalf of `signer`, until `deadline`.
+    ///
+    /// The signed permit can either be submitted with
+    /// [`LazyTokenSigner::permit`], or relayed to be submitted by someone
+    /// else.
+    ///
+    /// [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token doesn't support EIP-2612, or if the
+    /// signer fails.
+    pub async fn sign_permit<S: Signer>(
+        &self,
+        signer: &S,
+        spender: Address,
+        value: U256,
+        deadline: U256,
+    ) -> Result<SignedPermit, Error> {
+        let owner = signer.address();
+        let domain = self.eip712_domain().await?;
+        let permit = Permit {
+            owner,
+            spender,
+            value,
+            nonce: self.nonces(owner).await?,
+            deadline,
+        };
+
+        let signature = signer
+            .sign_hash(&permit.eip712_signing_hash(&domain))
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))?;
+
+        Ok(SignedPermit { permit, signature })
+    }
+
+    /// Submits a signed [EIP-2612] permit.
+    ///
+    /// The sender doesn't need to be the permit owner, which allows
+    /// relaying permits signed by someone else.
+    ///
+    /// [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn permit(
+        &self,
+        signed: &SignedPermit,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        let SignedPermit { permit, signature } = signed;
+
+        IERC20Permit::new(*self.address(), self.provider())
+            .permit(
+                permit.owner,
+                permit.spender,
+                permit.value,
+                permit.deadline,
+                27 + signature.v() as u8,
+                signature.r().into(),
+                signature.s().into(),
+            )
+            .send()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))
+    }
+}
+
+/// Builds an [`Eip712Domain`] from the fields flagged as used by an
+/// [ERC-5267](https://eips.ethereum.org/EIPS/eip-5267) domain.
+fn domain_from_erc5267(domain: IERC5267::eip712DomainReturn) -> Eip712Domain {
+    let fields = domain.fields[0];
+    let has = |bit: u8| fields & (1 << bit) != 0;
+
+    Eip712Domain::new(
+        has(0).then(|| domain.name.into()),
+        has(1).then(|| domain.version.into()),
+        has(2).then_some(domain.chainId),
+        has(3).then_some(domain.verifyingContract),
+        has(4).then_some(domain.salt),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{address, b256, FixedBytes, U256},
+        sol_types::SolStruct,
+    };
+
+    use super::{domain_from_erc5267, Permit, IERC5267};
+
+    #[test]
+    fn test_permit_typehash() {
+        let permit = Permit {
+            owner: Default::default(),
+            spender: Default::default(),
+            value: U256::ZERO,
+            nonce: U256::ZERO,
+            deadline: U256::ZERO,
+        };
+
+        assert_eq!(
+            permit.eip712_type_hash(),
+            b256!("6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9")
+        );
+    }
+
+    #[test]
+    fn test_domain_from_erc5267() {
+        let verifying_contract = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let domain = domain_from_erc5267(IERC5267::eip712DomainReturn {
+            fields: FixedBytes([0x0f]),
+            name: "USD Coin".to_string(),
+            version: "2".to_string(),
+            chainId: U256::from(1),
+            verifyingContract: verifying_contract,
+            salt: Default::default(),
+            extensions: Vec::new(),
+        });
+
+        assert_eq!(domain.name.as_deref(), Some("USD Coin"));
+        assert_eq!(domain.version.as_deref(), Some("2"));
+        assert_eq!(domain.chain_id, Some(U256::from(1)));
+        assert_eq!(domain.verifying_contract, Some(verifying_contract));
+        assert_eq!(domain.salt, None);
+    }
+}
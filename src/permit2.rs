@@ -0,0 +1,393 @@
+use alloy::{
+    network::Network,
+    primitives::{
+        address,
+        aliases::{U160, U48},
+        Address, Signature, U256,
+    },
+    providers::{PendingTransactionBuilder, Provider},
+    signers::Signer,
+    sol,
+    sol_types::{Eip712Domain, SolStruct},
+};
+
+use crate::{provider::Erc20Contract, Error, TokenId};
+
+/// The [Permit2] contract address, identical on every chain it is deployed
+/// on.
+///
+/// [Permit2]: https://github.com/Uniswap/permit2
+pub const PERMIT2_ADDRESS: Address = address!("000000000022D473030F116dDEE9F6B43aC78BA3");
+
+sol! {
+    /// The details of a token allowance, as signed in [`PermitSingle`] and
+    /// [`PermitBatch`].
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct PermitDetails {
+        address token;
+        uint160 amount;
+        uint48 expiration;
+        uint48 nonce;
+    }
+
+    /// The `AllowanceTransfer` typed data granting an allowance over a
+    /// single token.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct PermitSingle {
+        PermitDetails details;
+        address spender;
+        uint256 sigDeadline;
+    }
+
+    /// The `AllowanceTransfer` typed data granting allowances over many
+    /// tokens.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct PermitBatch {
+        PermitDetails[] details;
+        address spender;
+        uint256 sigDeadline;
+    }
+
+    /// A token and amount, as signed in [`PermitTransferFrom`].
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct TokenPermissions {
+        address token;
+        uint256 amount;
+    }
+
+    /// The `SignatureTransfer` typed data allowing a one-time transfer.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct PermitTransferFrom {
+        TokenPermissions permitted;
+        address spender;
+        uint256 nonce;
+        uint256 deadline;
+    }
+
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IPermit2 {
+        function allowance(address user, address token, address spender) external view returns (uint160 amount, uint48 expiration, uint48 nonce);
+        function nonceBitmap(address owner, uint256 wordPos) external view returns (uint256);
+        function permit(address owner, PermitSingle memory permitSingle, bytes calldata signature) external;
+        function permit(address owner, PermitBatch memory permitBatch, bytes calldata signature) external;
+    }
+}
+
+/// A Permit2 allowance, as granted through the `AllowanceTransfer` flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permit2Allowance {
+    /// The allowed amount.
+    pub amount: U160,
+    /// The timestamp at which the allowance expires.
+    pub expiration: U48,
+    /// The nonce to sign the next permit with.
+    pub nonce: U48,
+}
+
+/// A signed Permit2 typed data, ready to be submitted or relayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedPermit2<T> {
+    /// The signed typed data.
+    pub permit: T,
+    /// The signature, from the tokens owner.
+    pub signature: Signature,
+}
+
+/// A [Permit2] contract instance.
+///
+/// Reading allowances and nonces only requires a provider, while submitting
+/// permits requires a signer-capable provider, as for [`LazyTokenSigner`].
+///
+/// [Permit2]: https://github.com/Uniswap/permit2
+/// [`LazyTokenSigner`]: crate::LazyTokenSigner
+#[derive(Debug)]
+pub struct Permit2<P, N> {
+    instance: IPermit2::IPermit2Instance<P, N>,
+}
+
+impl<P, N> Permit2<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Creates a new [`Permit2`] at [`PERMIT2_ADDRESS`].
+    pub const fn new(provider: P) -> Self {
+        Self::at(PERMIT2_ADDRESS, provider)
+    }
+
+    /// Creates a new [`Permit2`] at the given address.
+    pub const fn at(address: Address, provider: P) -> Self {
+        Self {
+            instance: IPermit2::new(address, provider),
+        }
+    }
+
+    /// Returns the Permit2 contract address.
+    pub const fn address(&self) -> &Address {
+        self.instance.address()
+    }
+
+    /// Returns the allowance granted by `owner` to `spender` over `token`.
+    pub async fn allowance(
+        &self,
+        owner: Address,
+        token: Address,
+        spender: Address,
+    ) -> Result<Permit2Allowance, Error> {
+        let allowance = self
+            .instance
+            .allowance(owner, token, spender)
+            .call()
+            .await
+            .map_err(|err| Error::new(token.into(), err))?;
+
+        Ok(Permit2Allowance {
+            amount: allowance.amount,
+            expiration: allowance.expiration,
+            nonce: allowance.nonce,
+        })
+    }
+
+    /// Returns the `SignatureTransfer` nonces bitmap of `owner` at
+    /// `word_pos`.
+    pub async fn nonce_bitmap(&self, owner: Address, word_pos: U256) -> Result<U256, Error> {
+        self.instance
+            .nonceBitmap(owner, word_pos)
+            .call()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))
+    }
+
+    /// Returns `true` if the `SignatureTransfer` `nonce` of `owner` has
+    /// already been used.
+    pub async fn is_nonce_used(&self, owner: Address, nonce: U256) -> Result<bool, Error> {
+        let bitmap = self.nonce_bitmap(owner, nonce >> 8).await?;
+
+        Ok(bitmap.bit((nonce & U256::from(0xff)).to::<usize>()))
+    }
+
+    /// Returns the allowance granted by `owner` to Permit2 over `token`,
+    /// with a plain ERC-20 `approve`.
+    pub async fn token_allowance(&self, token: Address, owner: Address) -> Result<U256, Error> {
+        Erc20Contract::new(token, self.instance.provider())
+            .allowance(owner, *self.address())
+            .call()
+            .await
+            .map_err(|err| Error::new(token.into(), err))
+    }
+
+    /// Returns `true` if `owner` approved Permit2 to spend at least `amount`
+    /// of `token`, which is required before using any permit.
+    pub async fn is_approved(
+        &self,
+        token: Address,
+        owner: Address,
+        amount: U256,
+    ) -> Result<bool, Error> {
+        Ok(self.token_allowance(token, owner).await? >= amount)
+    }
+
+    /// Returns the Permit2 [EIP-712] domain.
+    ///
+    /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+    pub async fn eip712_domain(&self) -> Result<Eip712Domain, Error> {
+        let chain_id = self
+            .instance
+            .provider()
+            .get_chain_id()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))?;
+
+        Ok(Eip712Domain::new(
+            Some("Permit2".into()),
+            None,
+            Some(U256::from(chain_id)),
+            Some(*self.address()),
+            None,
+        ))
+    }
+
+    /// Signs a [`PermitSingle`] allowing `spender` to transfer up to `amount`
+    /// of `token` on behalf of `signer` until `expiration`.
+    ///
+    /// The nonce is read from the current `signer` allowance.
+    pub async fn sign_permit_single<S: Signer>(
+        &self,
+        signer: &S,
+        token: Address,
+        amount: U160,
+        expiration: U48,
+        spender: Address,
+        sig_deadline: U256,
+    ) -> Result<SignedPermit2<PermitSingle>, Error> {
+        let nonce = self
+            .allowance(signer.address(), token, spender)
+            .await?
+            .nonce;
+
+        let permit = PermitSingle {
+            details: PermitDetails {
+                token,
+                amount,
+                expiration,
+                nonce,
+            },
+            spender,
+            sigDeadline: sig_deadline,
+        };
+
+        self.sign(signer, permit, token.into()).await
+    }
+
+    /// Signs a [`PermitBatch`] allowing `spender` to transfer each
+    /// `(token, amount, expiration)` on behalf of `signer`.
+    ///
+    /// The nonces are read from the current `signer` allowances.
+    pub async fn sign_permit_batch<S: Signer>(
+        &self,
+        signer: &S,
+        tokens: &[(Address, U160, U48)],
+        spender: Address,
+        sig_deadline: U256,
+    ) -> Result<SignedPermit2<PermitBatch>, Error> {
+        let mut details = Vec::with_capacity(tokens.len());
+
+        for (token, amount, expiration) in tokens {
+            let nonce = self
+                .allowance(signer.address(), *token, spender)
+                .await?
+                .nonce;
+
+            details.push(PermitDetails {
+                token: *token,
+                amount: *amount,
+                expiration: *expiration,
+                nonce,
+            });
+        }
+
+        let permit = PermitBatch {
+            details,
+            spender,
+            sigDeadline: sig_deadline,
+        };
+
+        self.sign(signer, permit, (*self.address()).into()).await
+    }
+
+    /// Signs a [`PermitTransferFrom`] allowing `spender` to transfer
+    /// `amount` of `token` once on behalf of `signer`.
+    ///
+    /// `SignatureTransfer` nonces are unordered: any unused `nonce` works,
+    /// see [`Permit2::is_nonce_used`].
+    pub async fn sign_permit_transfer_from<S: Signer>(
+        &self,
+        signer: &S,
+        token: Address,
+        amount: U256,
+        spender: Address,
+        nonce: U256,
+        deadline: U256,
+    ) -> Result<SignedPermit2<PermitTransferFrom>, Error> {
+        let permit = PermitTransferFrom {
+            permitted: TokenPermissions { token, amount },
+            spender,
+            nonce,
+            deadline,
+        };
+
+        self.sign(signer, permit, token.into()).await
+    }
+
+    /// Submits a signed [`PermitSingle`] on behalf of `owner`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn permit_single(
+        &self,
+        owner: Address,
+        signed: &SignedPermit2<PermitSingle>,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.instance
+            .permit_0(
+                owner,
+                signed.permit.clone(),
+                signed.signature.as_bytes().into(),
+            )
+            .send()
+            .await
+            .map_err(|err| Error::new(signed.permit.details.token.into(), err))
+    }
+
+    /// Submits a signed [`PermitBatch`] on behalf of `owner`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction fails to send.
+    pub async fn permit_batch(
+        &self,
+        owner: Address,
+        signed: &SignedPermit2<PermitBatch>,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.instance
+            .permit_1(
+                owner,
+                signed.permit.clone(),
+                signed.signature.as_bytes().into(),
+            )
+            .send()
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))
+    }
+
+    async fn sign<S: Signer, T: SolStruct>(
+        &self,
+        signer: &S,
+        permit: T,
+        token: TokenId,
+    ) -> Result<SignedPermit2<T>, Error> {
+        let domain = self.eip712_domain().await?;
+
+        let signature = signer
+            .sign_hash(&permit.eip712_signing_hash(&domain))
+            .await
+            .map_err(|err| Error::new(token, err))?;
+
+        Ok(SignedPermit2 { permit, signature })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{primitives::keccak256, sol_types::SolStruct};
+
+    use super::{PermitBatch, PermitSingle, PermitTransferFrom};
+
+    #[test]
+    fn test_typehashes() {
+        // From the Permit2 `PermitHash` library
+        assert_eq!(
+            PermitSingle::default().eip712_type_hash(),
+            keccak256(
+                "PermitSingle(PermitDetails details,address spender,uint256 sigDeadline)\
+                PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)"
+            )
+        );
+        assert_eq!(
+            PermitBatch::default().eip712_type_hash(),
+            keccak256(
+                "PermitBatch(PermitDetails[] details,address spender,uint256 sigDeadline)\
+                PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)"
+            )
+        );
+        assert_eq!(
+            PermitTransferFrom::default().eip712_type_hash(),
+            keccak256(
+                "PermitTransferFrom(TokenPermissions permitted,address spender,uint256 nonce,uint256 deadline)\
+                TokenPermissions(address token,uint256 amount)"
+            )
+        );
+    }
+}
@@ -0,0 +1,455 @@
+use std::collections::{hash_map, HashMap};
+
+use alloy::{
+    network::Network,
+    primitives::{address, b256, Address, B256, U256},
+    providers::Provider,
+    sol_types::{sol_data, SolCall, SolType},
+};
+
+use crate::{
+    authorization::IERC3009,
+    erc1363::{ERC1363_INTERFACE_ID, ERC165_INTERFACE_ID, IERC165, INVALID_INTERFACE_ID},
+    multicall::{decode, return_data, Batch, IMulticall3},
+    permit::IERC20Permit,
+    vault::IERC4626,
+    Error, LazyToken,
+};
+
+/// The [EIP-1967] implementation slot,
+/// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`.
+///
+/// [EIP-1967]: https://eips.ethereum.org/EIPS/eip-1967
+const EIP1967_IMPLEMENTATION_SLOT: B256 =
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// The [EIP-1967] beacon slot,
+/// `bytes32(uint256(keccak256("eip1967.proxy.beacon")) - 1)`.
+///
+/// [EIP-1967]: https://eips.ethereum.org/EIPS/eip-1967
+const EIP1967_BEACON_SLOT: B256 =
+    b256!("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50");
+
+/// The [EIP-1822] implementation slot, `keccak256("PROXIABLE")`.
+///
+/// [EIP-1822]: https://eips.ethereum.org/EIPS/eip-1822
+const EIP1822_IMPLEMENTATION_SLOT: B256 =
+    b256!("c5f16f0fcc639fa48a6947836d9850f504798523bf8c9a3a87d5876cf622bcf7");
+
+/// The account used as owner and spender when probing a token.
+const PROBE_ACCOUNT: Address = address!("000000000000000000000000000000000000dEaD");
+
+/// The maximum number of decimals of a sane token.
+const MAX_DECIMALS: u8 = 36;
+
+/// The proxy pattern of a token contract, detected from its storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Proxy {
+    /// An [EIP-1967] transparent or UUPS proxy.
+    ///
+    /// [EIP-1967]: https://eips.ethereum.org/EIPS/eip-1967
+    Eip1967 {
+        /// The implementation contract address.
+        implementation: Address,
+    },
+    /// An [EIP-1967] beacon proxy, whose implementation is provided by a
+    /// beacon contract.
+    ///
+    /// [EIP-1967]: https://eips.ethereum.org/EIPS/eip-1967
+    Beacon {
+        /// The beacon contract address.
+        beacon: Address,
+    },
+    /// An [EIP-1822] universal upgradeable proxy.
+    ///
+    /// [EIP-1822]: https://eips.ethereum.org/EIPS/eip-1822
+    Eip1822 {
+        /// The implementation contract address.
+        implementation: Address,
+    },
+}
+
+/// How a token answers `decimals()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecimalsStatus {
+    /// The token returned a sane number of decimals.
+    Valid(u8),
+    /// The call reverted, or didn't return a number.
+    Reverts,
+    /// The token returned more than 36 decimals, which is likely to
+    /// overflow amount computations.
+    OutOfRange(U256),
+}
+
+/// The capabilities and quirks of a token, probed from the blockchain with
+/// [`LazyToken::profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenProfile {
+    /// Whether there is code at the token address.
+    pub has_code: bool,
+    /// The proxy pattern of the token, if any.
+    pub proxy: Option<Proxy>,
+    /// How the token answers `decimals()`.
+    pub decimals: DecimalsStatus,
+    /// Whether the token implements [ERC-165].
+    ///
+    /// [ERC-165]: https://eips.ethereum.org/EIPS/eip-165
+    pub erc165: bool,
+    /// Whether the token supports [EIP-2612] permits.
+    ///
+    /// [EIP-2612]: https://eips.ethereum.org/EIPS/eip-2612
+    pub permit: bool,
+    /// Whether the token supports [EIP-3009] authorizations.
+    ///
+    /// [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+    pub erc3009: bool,
+    /// Whether the token advertises [ERC-1363] through ERC-165.
+    ///
+    /// [ERC-1363]: https://eips.ethereum.org/EIPS/eip-1363
+    pub erc1363: bool,
+    /// Whether the token is an [ERC-4626] vault.
+    ///
+    /// [ERC-4626]: https://eips.ethereum.org/EIPS/eip-4626
+    pub erc4626: bool,
+    /// Whether changing a non-zero allowance requires resetting it to zero
+    /// first, like USDT does, or `None` if approving fails altogether.
+    pub approve_requires_reset: Option<bool>,
+}
+
+impl TokenProfile {
+    /// The profile of the native asset, which is no contract.
+    const fn native() -> Self {
+        Self {
+            has_code: false,
+            proxy: None,
+            decimals: DecimalsStatus::Valid(18),
+            erc165: false,
+            permit: false,
+            erc3009: false,
+            erc1363: false,
+            erc4626: false,
+            approve_requires_reset: None,
+        }
+    }
+}
+
+impl<P, N> LazyToken<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Probes the token capabilities and quirks, see [`TokenProfile`].
+    ///
+    /// The code and the proxy slots are first read in separate, sequential,
+    /// round-trips, then all the other probes are made in a single
+    /// Multicall3 round-trip. `approve` is simulated from the Multicall3
+    /// contract, changing an allowance twice, so no state is altered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails, or if Multicall3 doesn't return
+    /// one result per probe.
+    pub async fn profile(&self) -> Result<TokenProfile, Error> {
+        if self.is_native() {
+            return Ok(TokenProfile::native());
+        }
+
+        let address = *self.address();
+        let provider = self.provider();
+
+        let has_code = !provider
+            .get_code_at(address)
+            .await
+            .map_err(|err| Error::new(address.into(), err))?
+            .is_empty();
+
+        if !has_code {
+            return Ok(TokenProfile {
+                has_code,
+                decimals: DecimalsStatus::Reverts,
+                ..TokenProfile::native()
+            });
+        }
+
+        let proxy = self.proxy().await?;
+
+        let mut batch = Batch::default();
+        batch.push_raw(address, self.instance.decimals().calldata().clone());
+        batch.push(address, IERC20Permit::DOMAIN_SEPARATORCall {});
+        batch.push(
+            address,
+            IERC20Permit::noncesCall {
+                owner: PROBE_ACCOUNT,
+            },
+        );
+        batch.push(
+            address,
+            IERC3009::authorizationStateCall {
+                authorizer: PROBE_ACCOUNT,
+                nonce: B256::ZERO,
+            },
+        );
+        for interface_id in [
+            ERC165_INTERFACE_ID,
+            INVALID_INTERFACE_ID,
+            ERC1363_INTERFACE_ID,
+        ] {
+            batch.push(
+                address,
+                IERC165::supportsInterfaceCall {
+                    interfaceId: interface_id,
+                },
+            );
+        }
+        batch.push(address, IERC4626::assetCall {});
+        batch.push(address, IERC4626::totalAssetsCall {});
+        for amount in [1, 2] {
+            batch.push_raw(
+                address,
+                self.instance
+                    .approve(PROBE_ACCOUNT, U256::from(amount))
+                    .calldata()
+                    .clone(),
+            );
+        }
+
+        let results = batch
+            .call(provider)
+            .await
+            .map_err(|err| Error::new(address.into(), err))?;
+
+        let results = <[IMulticall3::Result; 11]>::try_from(results).map_err(|results| {
+            let err = alloy::sol_types::Error::custom(format!(
+                "expected 11 Multicall3 results, got {}",
+                results.len()
+            ));
+            Error::new(address.into(), err)
+        })?;
+        let [decimals, separator, nonces, authorization, rest @ ..] = &results;
+        let [erc165, invalid, erc1363, asset, assets, approve, reapprove] = rest;
+
+        let erc165 = decode::<IERC165::supportsInterfaceCall>(erc165).unwrap_or_default()
+            && !decode::<IERC165::supportsInterfaceCall>(invalid).unwrap_or(true);
+
+        Ok(TokenProfile {
+            has_code,
+            proxy,
+            decimals: decimals_status(decimals),
+            erc165,
+            permit: succeeds::<IERC20Permit::DOMAIN_SEPARATORCall>(separator)
+                && succeeds::<IERC20Permit::noncesCall>(nonces),
+            erc3009: succeeds::<IERC3009::authorizationStateCall>(authorization),
+            erc1363: erc165
+                && decode::<IERC165::supportsInterfaceCall>(erc1363).unwrap_or_default(),
+            erc4626: succeeds::<IERC4626::assetCall>(asset)
+                && succeeds::<IERC4626::totalAssetsCall>(assets),
+            approve_requires_reset: approved(approve).then(|| !approved(reapprove)),
+        })
+    }
+
+    /// Returns the proxy pattern of the token, read from its storage.
+    async fn proxy(&self) -> Result<Option<Proxy>, Error> {
+        let eip1967 = self.read_address_slot(EIP1967_IMPLEMENTATION_SLOT).await?;
+        if !eip1967.is_zero() {
+            return Ok(Some(Proxy::Eip1967 {
+                implementation: eip1967,
+            }));
+        }
+
+        let beacon = self.read_address_slot(EIP1967_BEACON_SLOT).await?;
+        if !beacon.is_zero() {
+            return Ok(Some(Proxy::Beacon { beacon }));
+        }
+
+        let eip1822 = self.read_address_slot(EIP1822_IMPLEMENTATION_SLOT).await?;
+        if !eip1822.is_zero() {
+            return Ok(Some(Proxy::Eip1822 {
+                implementation: eip1822,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    async fn read_address_slot(&self, slot: B256) -> Result<Address, Error> {
+        let value = self
+            .provider()
+            .get_storage_at(*self.address(), slot.into())
+            .await
+            .map_err(|err| Error::new((*self.address()).into(), err))?;
+
+        Ok(Address::from_word(value.into()))
+    }
+}
+
+/// Returns `true` if a call succeeded with a well-formed return value.
+fn succeeds<C: SolCall>(result: &IMulticall3::Result) -> bool {
+    decode::<C>(result).is_ok()
+}
+
+/// Returns `true` if an `approve` call succeeded, tolerating tokens
+/// returning nothing.
+fn approved(result: &IMulticall3::Result) -> bool {
+    match return_data(result) {
+        Ok([]) => true,
+        Ok(data) => <sol_data::Bool as SolType>::abi_decode(data).unwrap_or_default(),
+        Err(_) => false,
+    }
+}
+
+/// Decodes `decimals()` as a `uint256`, to catch out of range values.
+fn decimals_status(result: &IMulticall3::Result) -> DecimalsStatus {
+    let Some(decimals) = return_data(result)
+        .ok()
+        .and_then(|data| <sol_data::Uint<256> as SolType>::abi_decode(data).ok())
+    else {
+        return DecimalsStatus::Reverts;
+    };
+
+    match u8::try_from(decimals) {
+        Ok(decimals) if decimals <= MAX_DECIMALS => DecimalsStatus::Valid(decimals),
+        _ => DecimalsStatus::OutOfRange(decimals),
+    }
+}
+
+/// A cache of [`TokenProfile`]s, keyed by chain id and token address.
+///
+/// Profiles rarely change, except for upgradeable proxies, so they can be
+/// probed once and reused, see [`TokenProfileCache::get_or_probe`].
+#[derive(Debug, Default, Clone)]
+pub struct TokenProfileCache {
+    profiles: HashMap<(u64, Address), TokenProfile>,
+}
+
+impl TokenProfileCache {
+    /// Creates a new empty [`TokenProfileCache`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the profile of the token at `address`, if cached.
+    pub fn get(&self, chain_id: u64, address: Address) -> Option<&TokenProfile> {
+        self.profiles.get(&(chain_id, address))
+    }
+
+    /// Caches the profile of the token at `address`, returning the
+    /// previously cached one.
+    pub fn insert(
+        &mut self,
+        chain_id: u64,
+        address: Address,
+        profile: TokenProfile,
+    ) -> Option<TokenProfile> {
+        self.profiles.insert((chain_id, address), profile)
+    }
+
+    /// Removes the profile of the token at `address`, to probe it again.
+    pub fn remove(&mut self, chain_id: u64, address: Address) -> Option<TokenProfile> {
+        self.profiles.remove(&(chain_id, address))
+    }
+
+    /// Returns `true` if the profile of the token at `address` is cached.
+    pub fn contains(&self, chain_id: u64, address: Address) -> bool {
+        self.profiles.contains_key(&(chain_id, address))
+    }
+
+    /// Returns the number of cached profiles.
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    /// Returns `true` if no profile is cached.
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+
+    /// Returns the cached profile of `token`, probing and caching it if
+    /// missing.
+    pub async fn get_or_probe<P, N>(
+        &mut self,
+        token: &LazyToken<P, N>,
+    ) -> Result<&TokenProfile, Error>
+    where
+        P: Provider<N>,
+        N: Network,
+    {
+        let chain_id = token
+            .provider()
+            .get_chain_id()
+            .await
+            .map_err(|err| Error::new((*token.address()).into(), err))?;
+
+        match self.profiles.entry((chain_id, *token.address())) {
+            hash_map::Entry::Occupied(entry) => Ok(entry.into_mut()),
+            hash_map::Entry::Vacant(entry) => Ok(entry.insert(token.profile().await?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{keccak256, U256},
+        sol_types::{sol_data, SolType},
+    };
+
+    use super::{
+        decimals_status, DecimalsStatus, EIP1822_IMPLEMENTATION_SLOT, EIP1967_BEACON_SLOT,
+        EIP1967_IMPLEMENTATION_SLOT,
+    };
+    use crate::multicall::IMulticall3;
+
+    fn slot(label: &str) -> U256 {
+        U256::from_be_bytes(keccak256(label).0) - U256::from(1)
+    }
+
+    fn result(success: bool, decimals: U256) -> IMulticall3::Result {
+        IMulticall3::Result {
+            success,
+            returnData: <sol_data::Uint<256> as SolType>::abi_encode(&decimals).into(),
+        }
+    }
+
+    #[test]
+    fn test_proxy_slots() {
+        assert_eq!(
+            EIP1967_IMPLEMENTATION_SLOT,
+            slot("eip1967.proxy.implementation").to_be_bytes::<32>()
+        );
+        assert_eq!(
+            EIP1967_BEACON_SLOT,
+            slot("eip1967.proxy.beacon").to_be_bytes::<32>()
+        );
+        assert_eq!(EIP1822_IMPLEMENTATION_SLOT, keccak256("PROXIABLE"));
+    }
+
+    #[test]
+    fn test_decimals_status() {
+        assert_eq!(
+            decimals_status(&result(true, U256::from(6))),
+            DecimalsStatus::Valid(6)
+        );
+        assert_eq!(
+            decimals_status(&result(true, U256::from(77))),
+            DecimalsStatus::OutOfRange(U256::from(77))
+        );
+        assert_eq!(
+            decimals_status(&result(true, U256::MAX)),
+            DecimalsStatus::OutOfRange(U256::MAX)
+        );
+        assert_eq!(
+            decimals_status(&result(false, U256::from(6))),
+            DecimalsStatus::Reverts
+        );
+        assert_eq!(
+            decimals_status(&IMulticall3::Result {
+                success: true,
+                returnData: Default::default(),
+            }),
+            DecimalsStatus::Reverts
+        );
+    }
+}
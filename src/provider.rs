@@ -1,7 +1,23 @@
-use crate::{error::InternalError, stores::TokenStore, Entry, Error, Token, TokenId};
-use alloy::{network::Network, primitives::Address, providers::Provider, sol};
+use crate::{
+    error::InternalError,
+    events,
+    multicall::{self, Batch},
+    native_token,
+    stores::{SharedTokenStore, TokenStore},
+    units, ApprovalEvent, DecodedString, DecodedToken, Entry, Error, SymbolResolver, Token,
+    TokenId, TransferEvent, NATIVE_ADDRESS,
+};
+use alloy::{
+    eips::BlockId,
+    network::Network,
+    primitives::Address,
+    providers::{Provider, MULTICALL3_ADDRESS},
+    sol,
+};
 use async_trait::async_trait;
 use bigdecimal::BigDecimal;
+use futures::{stream::BoxStream, StreamExt};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
 
 sol!(
     #[sol(rpc)]
@@ -16,24 +32,118 @@ where
     N: Network,
 {
     /// Retrieves a token by querying its ERC-20 contract.
+    ///
+    /// Legacy tokens returning their symbol as a `bytes32` (like MKR) are
+    /// supported, see [`DecodedString::decode`]. The native asset is
+    /// returned without querying any contract, see [`native_token`].
+    ///
+    /// See [`Erc20ProviderExt::retrieve_token_decoded`] to know how the
+    /// symbol was encoded.
     async fn retrieve_token(&self, address: Address) -> Result<Token, Error> {
+        Ok(self.retrieve_token_decoded(address).await?.token)
+    }
+
+    /// Retrieves a token by querying its ERC-20 contract, along with the
+    /// encoding its symbol was returned with.
+    ///
+    /// See [`Erc20ProviderExt::retrieve_token`].
+    async fn retrieve_token_decoded(&self, address: Address) -> Result<DecodedToken, Error> {
+        if address == NATIVE_ADDRESS {
+            let chain_id = self
+                .get_chain_id()
+                .await
+                .map_err(|err| Error::new(address.into(), err))?;
+
+            return Ok(DecodedToken::native(native_token(chain_id)));
+        }
+
         let instance = Erc20Contract::Erc20ContractInstance::new(address, self);
 
         let symbol = instance
             .symbol()
-            .call()
+            .call_raw()
             .await
             .map_err(|err| Error::new(address.into(), err))?;
 
+        let symbol =
+            DecodedString::decode(&symbol).map_err(|err| Error::new(address.into(), err))?;
+
         let decimals = instance
             .decimals()
             .call()
             .await
             .map_err(|err| Error::new(address.into(), err))?;
 
-        let token = Token::new(address, symbol, decimals);
+        Ok(DecodedToken {
+            token: Token::new(address, symbol.value, decimals),
+            symbol_encoding: symbol.encoding,
+            name_encoding: None,
+        })
+    }
+
+    /// Retrieves many tokens at once, batching their `symbol`, `decimals`
+    /// and `name` calls in a single [Multicall3] round-trip.
+    ///
+    /// A failing token doesn't fail the whole batch: one result is returned
+    /// per requested address, in the same order. The `name` is optional in
+    /// the ERC-20 standard, so failing to retrieve it leaves
+    /// [`Token::name`] empty rather than failing the token.
+    ///
+    /// See [`Erc20ProviderExt::retrieve_tokens_decoded`] to know how the
+    /// symbols and names were encoded.
+    ///
+    /// [Multicall3]: https://github.com/mds1/multicall
+    async fn retrieve_tokens(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<Result<Token, Error>>, alloy::contract::Error> {
+        let tokens = self.retrieve_tokens_decoded(addresses).await?;
+
+        Ok(tokens
+            .into_iter()
+            .map(|token| token.map(|token| token.token))
+            .collect())
+    }
+
+    /// Retrieves many tokens at once, along with the encodings their symbol
+    /// and name were returned with.
+    ///
+    /// See [`Erc20ProviderExt::retrieve_tokens`].
+    async fn retrieve_tokens_decoded(
+        &self,
+        addresses: &[Address],
+    ) -> Result<Vec<Result<DecodedToken, Error>>, alloy::contract::Error> {
+        let native = if addresses.contains(&NATIVE_ADDRESS) {
+            Some(native_token(self.get_chain_id().await?))
+        } else {
+            None
+        };
+        let mut batch = Batch::default();
+
+        for address in addresses
+            .iter()
+            .filter(|address| **address != NATIVE_ADDRESS)
+        {
+            batch.push(*address, Erc20Contract::symbolCall {});
+            batch.push(*address, Erc20Contract::decimalsCall {});
+            batch.push(*address, Erc20Contract::nameCall {});
+        }
+
+        // The batch returns one result per call, three per token
+        let results = batch.call(self).await?;
+        let mut results = results.chunks_exact(3);
+
+        let tokens = addresses
+            .iter()
+            .map(|address| match &native {
+                Some(native) if *address == NATIVE_ADDRESS => {
+                    Ok(DecodedToken::native(native.clone()))
+                }
+                _ => token_from_batch(*address, results.next().expect("one result per token")),
+            })
+            .collect();
 
-        Ok(token)
+        Ok(tokens)
     }
 
     /// Returns a token from the given store if present, otherwise retrieves
@@ -59,27 +169,379 @@ where
                     }
                 }?;
 
-                Ok(e.insert(token))
+                e.insert(token).map(|token| &*token)
+            }
+        }
+    }
+
+    /// Returns a token from the given store if present, otherwise retrieves
+    /// it from its ERC-20 contract and update the store.
+    ///
+    /// Unlike [`Erc20ProviderExt::get_token`], symbols missing from the
+    /// store are looked up with `resolver`. Symbols shared by several tokens,
+    /// in the store or according to `resolver`, fail with
+    /// [`InternalError::AmbiguousSymbol`].
+    ///
+    /// Fails with [`InternalError::SymbolConflict`] if the store rejects the
+    /// resolved token, see [`TokenStore::try_insert`].
+    async fn get_token_with_resolver<'a, Id, S, R>(
+        &'a self,
+        id: Id,
+        store: &'a mut S,
+        resolver: &R,
+    ) -> Result<&'a Token, Error>
+    where
+        S: for<'b> TokenStore<'b> + Send,
+        Id: Into<TokenId> + Send,
+        R: SymbolResolver + ?Sized,
+    {
+        let id: TokenId = id.into();
+        let chain_id = self
+            .get_chain_id()
+            .await
+            .map_err(|err| Error::new(id.clone(), err))?;
+
+        if let TokenId::Symbol(symbol) = &id {
+            let candidates = store
+                .candidates(chain_id, symbol)
+                .iter()
+                .map(|token| token.address)
+                .collect::<Vec<_>>();
+
+            if candidates.len() > 1 {
+                return Err(Error::new(id, InternalError::AmbiguousSymbol(candidates)));
+            }
+        }
+
+        if store.contains(chain_id, id.clone()) {
+            return Ok(store.get_mut(chain_id, id).expect("token in store"));
+        }
+
+        let address = match &id {
+            TokenId::Address(address) => *address,
+            TokenId::Symbol(symbol) => {
+                let mut addresses = resolver.resolve(chain_id, symbol).await?;
+                addresses.sort();
+                addresses.dedup();
+
+                match addresses.as_slice() {
+                    [] => {
+                        return Err(Error::new(
+                            id.clone(),
+                            InternalError::NotInStore(symbol.clone()),
+                        ))
+                    }
+                    [address] => *address,
+                    _ => return Err(Error::new(id, InternalError::AmbiguousSymbol(addresses))),
+                }
+            }
+        };
+
+        let token = self.retrieve_token(address).await?;
+        store.try_insert(chain_id, token)?;
+
+        // The resolved token is looked up by address, as its symbol may
+        // differ from the requested one, e.g. when it differs in case.
+        store
+            .get_mut(chain_id, address.into())
+            .map(|token| &*token)
+            .ok_or_else(|| Error::new(id, InternalError::NotInStore(address.to_string())))
+    }
+
+    /// Returns a token from the given shared store if present, otherwise
+    /// retrieves it from its ERC-20 contract and update the store.
+    ///
+    /// Unlike [`Erc20ProviderExt::get_token`], the store is only borrowed
+    /// immutably, so it can be used from many tasks at once. Concurrent
+    /// misses for the same token are deduplicated by stores supporting it,
+    /// like [`ConcurrentTokenStore`].
+    ///
+    /// [`ConcurrentTokenStore`]: crate::ConcurrentTokenStore
+    async fn get_token_shared<Id, S>(&self, id: Id, store: &S) -> Result<Arc<Token>, Error>
+    where
+        S: SharedTokenStore,
+        Id: Into<TokenId> + Send,
+    {
+        let id: TokenId = id.into();
+        let chain_id = self
+            .get_chain_id()
+            .await
+            .map_err(|err| Error::new(id.clone(), err))?;
+
+        match id {
+            TokenId::Address(address) => {
+                store
+                    .get_or_fetch(chain_id, address, self.retrieve_token(address))
+                    .await
             }
+            TokenId::Symbol(ref symbol) => store
+                .get(chain_id, &id)
+                .ok_or_else(|| Error::new(id.clone(), InternalError::NotInStore(symbol.clone()))),
         }
     }
 
     /// Retrieves the given address balance from the given token contract.
     async fn balance_of(&self, token: Address, address: Address) -> Result<BigDecimal, Error> {
+        self.balance_of_at(token, address, BlockId::latest()).await
+    }
+
+    /// Retrieves the given address balance from the given token contract at
+    /// `block`.
+    ///
+    /// The token decimals are read at the same `block`. The balance of the
+    /// native asset is read with `eth_getBalance`.
+    async fn balance_of_at(
+        &self,
+        token: Address,
+        address: Address,
+        block: BlockId,
+    ) -> Result<BigDecimal, Error> {
+        if token == NATIVE_ADDRESS {
+            let balance = self
+                .get_balance(address)
+                .block_id(block)
+                .await
+                .map_err(|err| Error::new(address.into(), err))?;
+
+            return Ok(self.retrieve_token(token).await?.get_balance(balance));
+        }
+
         let instance = Erc20Contract::Erc20ContractInstance::new(token, self);
 
         let result = instance
             .balanceOf(address)
+            .block(block)
             .call()
             .await
             .map_err(|err| Error::new(address.into(), err))?;
 
-        let token = self.retrieve_token(token).await?;
+        let decimals = instance
+            .decimals()
+            .block(block)
+            .call()
+            .await
+            .map_err(|err| Error::new(token.into(), err))?;
 
-        let balance = token.get_balance(result);
+        let balance = units::to_decimal(result, decimals);
 
         Ok(balance)
     }
+
+    /// Retrieves the balances of many `(token, holder)` pairs in a single
+    /// [Multicall3] round-trip.
+    ///
+    /// Token decimals are read from the given store. Tokens missing from it
+    /// are retrieved within the same round-trip, and inserted into the store,
+    /// but for the native asset, which is never stored.
+    /// One result is returned per pair, keyed by `(token, holder)`.
+    ///
+    /// Native asset balances are read with the Multicall3 `getEthBalance`.
+    ///
+    /// [Multicall3]: https://github.com/mds1/multicall
+    async fn balances_of<S>(
+        &self,
+        pairs: &[(Address, Address)],
+        store: &mut S,
+    ) -> Result<HashMap<(Address, Address), Result<BigDecimal, Error>>, alloy::contract::Error>
+    where
+        S: for<'a> TokenStore<'a> + Send,
+    {
+        self.balances_of_at(pairs, BlockId::latest(), store).await
+    }
+
+    /// Retrieves the balances of many `(token, holder)` pairs at `block`, in
+    /// a single [Multicall3] round-trip.
+    ///
+    /// See [`Erc20ProviderExt::balances_of`].
+    ///
+    /// [Multicall3]: https://github.com/mds1/multicall
+    async fn balances_of_at<S>(
+        &self,
+        pairs: &[(Address, Address)],
+        block: BlockId,
+        store: &mut S,
+    ) -> Result<HashMap<(Address, Address), Result<BigDecimal, Error>>, alloy::contract::Error>
+    where
+        S: for<'a> TokenStore<'a> + Send,
+    {
+        let chain_id = self.get_chain_id().await?;
+        let mut batch = Batch::default();
+        let mut missing = Vec::new();
+
+        let native = native_token(chain_id);
+
+        for (token, holder) in pairs {
+            if *token == NATIVE_ADDRESS {
+                batch.push(
+                    MULTICALL3_ADDRESS,
+                    multicall::IMulticall3::getEthBalanceCall { addr: *holder },
+                );
+                continue;
+            }
+
+            batch.push(*token, Erc20Contract::balanceOfCall { _owner: *holder });
+
+            if !missing.contains(token) && !store.contains(chain_id, (*token).into()) {
+                missing.push(*token);
+            }
+        }
+
+        for token in &missing {
+            batch.push(*token, Erc20Contract::symbolCall {});
+            batch.push(*token, Erc20Contract::decimalsCall {});
+            batch.push(*token, Erc20Contract::nameCall {});
+        }
+
+        // The batch returns one result per call, so at least one per pair
+        let results = batch.call_at(self, block).await?;
+        let (balances, metadata) = results.split_at(pairs.len());
+
+        let metadata = missing
+            .into_iter()
+            .zip(metadata.chunks_exact(3))
+            .collect::<HashMap<_, _>>();
+
+        // Every token is resolved before updating the store, as inserting
+        // the missing ones may evict others
+        let mut tokens = metadata
+            .iter()
+            .filter_map(|(address, results)| {
+                Some((*address, token_from_batch(*address, results).ok()?.token))
+            })
+            .collect::<HashMap<_, _>>();
+
+        for (token, _) in pairs {
+            if tokens.contains_key(token) {
+                continue;
+            }
+
+            if let Some(stored) = store.get(chain_id, (*token).into()) {
+                tokens.insert(*token, Token::clone(&stored));
+            } else if *token == NATIVE_ADDRESS {
+                tokens.insert(*token, native.clone());
+            }
+        }
+
+        // The native pseudo-token is built locally, and never stored
+        for token in tokens
+            .values()
+            .filter(|token| token.address != NATIVE_ADDRESS)
+        {
+            if !store.contains(chain_id, token.address.into()) {
+                // A rejected token is still used for this call
+                let _ = store.try_insert(chain_id, token.clone());
+            }
+        }
+
+        let balances = pairs
+            .iter()
+            .zip(balances)
+            .map(|((token, holder), result)| {
+                let balance = if *token == NATIVE_ADDRESS {
+                    multicall::decode::<multicall::IMulticall3::getEthBalanceCall>(result)
+                } else {
+                    multicall::decode::<Erc20Contract::balanceOfCall>(result)
+                };
+
+                let balance = balance
+                    .map_err(|err| Error::new((*token).into(), err))
+                    .and_then(|balance| match (tokens.get(token), metadata.get(token)) {
+                        (Some(token), _) => Ok(token.get_balance(balance)),
+                        // Decoding again yields the reason the token couldn't be retrieved
+                        (None, Some(results)) => token_from_batch(*token, results)
+                            .map(|token| token.token.get_balance(balance)),
+                        (None, None) => Err(Error::new(
+                            (*token).into(),
+                            InternalError::NotInStore(token.to_string()),
+                        )),
+                    });
+
+                ((*token, *holder), balance)
+            })
+            .collect();
+
+        Ok(balances)
+    }
+
+    /// Streams the `Transfer` events emitted by `token` over `blocks`.
+    ///
+    /// The token is read from the given store, or retrieved and inserted into
+    /// it if missing, so that each event carries the token decimals: the
+    /// returned stream doesn't borrow the store. Block ranges rejected by the
+    /// node as too large are split in halves and queried in turn.
+    async fn transfer_events<'a, S>(
+        &'a self,
+        token: Address,
+        blocks: RangeInclusive<u64>,
+        store: &mut S,
+    ) -> Result<BoxStream<'a, Result<TransferEvent, Error>>, Error>
+    where
+        S: for<'b> TokenStore<'b> + Send,
+    {
+        let token = self.get_token(token, store).await?.clone();
+        let filter = events::event_filter::<Erc20Contract::Transfer>(token.address);
+
+        let events = events::logs(self, filter, blocks).map(move |log| {
+            let id = TokenId::from(token.address);
+            let log = log.map_err(|err| Error::new(id.clone(), err))?;
+
+            TransferEvent::decode(token.clone(), &log).map_err(|err| Error::new(id, err))
+        });
+
+        Ok(events.boxed())
+    }
+
+    /// Streams the `Approval` events emitted by `token` over `blocks`.
+    ///
+    /// See [`Erc20ProviderExt::transfer_events`] for how the token and the
+    /// block range are handled.
+    async fn approval_events<'a, S>(
+        &'a self,
+        token: Address,
+        blocks: RangeInclusive<u64>,
+        store: &mut S,
+    ) -> Result<BoxStream<'a, Result<ApprovalEvent, Error>>, Error>
+    where
+        S: for<'b> TokenStore<'b> + Send,
+    {
+        let token = self.get_token(token, store).await?.clone();
+        let filter = events::event_filter::<Erc20Contract::Approval>(token.address);
+
+        let events = events::logs(self, filter, blocks).map(move |log| {
+            let id = TokenId::from(token.address);
+            let log = log.map_err(|err| Error::new(id.clone(), err))?;
+
+            ApprovalEvent::decode(token.clone(), &log).map_err(|err| Error::new(id, err))
+        });
+
+        Ok(events.boxed())
+    }
+}
+
+/// Builds a token from the `symbol`, `decimals` and `name` results of a
+/// [`Batch`].
+fn token_from_batch(
+    address: Address,
+    results: &[multicall::IMulticall3::Result],
+) -> Result<DecodedToken, Error> {
+    let symbol =
+        multicall::decode_string(&results[0]).map_err(|err| Error::new(address.into(), err))?;
+    let decimals = multicall::decode::<Erc20Contract::decimalsCall>(&results[1])
+        .map_err(|err| Error::new(address.into(), err))?;
+    let token = Token::new(address, symbol.value, decimals);
+
+    Ok(match multicall::decode_string(&results[2]) {
+        Ok(name) => DecodedToken {
+            token: token.with_name(name.value),
+            symbol_encoding: symbol.encoding,
+            name_encoding: Some(name.encoding),
+        },
+        Err(_) => DecodedToken {
+            token,
+            symbol_encoding: symbol.encoding,
+            name_encoding: None,
+        },
+    })
 }
 
 #[async_trait]
@@ -0,0 +1,237 @@
+use std::marker::PhantomData;
+
+use alloy::{
+    network::Network,
+    primitives::{address, keccak256, Address, B256},
+    providers::Provider,
+    sol,
+};
+use async_trait::async_trait;
+
+use crate::{error::InternalError, Error, TokenId};
+
+sol! {
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IEnsRegistry {
+        function resolver(bytes32 node) external view returns (address);
+    }
+
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IEnsResolver {
+        function addr(bytes32 node) external view returns (address);
+    }
+}
+
+/// The address of the ENS registry, the same on Ethereum mainnet and
+/// testnets.
+pub const ENS_REGISTRY_ADDRESS: Address = address!("00000000000C2E074eC69A0dFb2997BA6C7d2e1e");
+
+/// Resolves token symbols missing from a store to token addresses.
+///
+/// See [`Erc20ProviderExt::get_token_with_resolver`].
+///
+/// Closures taking a chain id and a symbol and returning the matching
+/// addresses are resolvers too.
+///
+/// [`Erc20ProviderExt::get_token_with_resolver`]: crate::Erc20ProviderExt::get_token_with_resolver
+#[async_trait]
+pub trait SymbolResolver: Send + Sync {
+    /// Returns the addresses of the tokens with the given `symbol` on
+    /// `chain_id`, or an empty list if the symbol is unknown.
+    async fn resolve(&self, chain_id: u64, symbol: &str) -> Result<Vec<Address>, Error>;
+}
+
+#[async_trait]
+impl<F> SymbolResolver for F
+where
+    F: Fn(u64, &str) -> Vec<Address> + Send + Sync,
+{
+    async fn resolve(&self, chain_id: u64, symbol: &str) -> Result<Vec<Address>, Error> {
+        Ok(self(chain_id, symbol))
+    }
+}
+
+#[cfg(feature = "token-list")]
+#[async_trait]
+impl SymbolResolver for crate::TokenList {
+    async fn resolve(&self, chain_id: u64, symbol: &str) -> Result<Vec<Address>, Error> {
+        Ok(self
+            .tokens
+            .iter()
+            .filter(|token| token.chain_id == chain_id && token.symbol == symbol)
+            .map(|token| token.address)
+            .collect())
+    }
+}
+
+/// Resolves symbols as ENS names under a parent name, like
+/// `usdc.tkn.eth` for `USDC` under `tkn.eth`.
+///
+/// Symbols are only resolved on the chain of the provider. They are
+/// restricted to ASCII letters, digits, `-`, `_` and `$`, which ENSIP-15
+/// normalizes by lowercasing, with `.` separating labels. Other symbols fail
+/// with [`InternalError::InvalidEnsName`] rather than resolving to a name
+/// their owner may not have registered.
+///
+/// [`InternalError::InvalidEnsName`]: crate::InternalError::InvalidEnsName
+#[derive(Debug, Clone)]
+pub struct EnsResolver<P, N> {
+    provider: P,
+    parent: String,
+    _network: PhantomData<N>,
+}
+
+impl<P, N> EnsResolver<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Creates a new [`EnsResolver`] resolving symbols under `parent`.
+    pub fn new(provider: P, parent: impl Into<String>) -> Self {
+        Self {
+            provider,
+            parent: parent.into(),
+            _network: PhantomData,
+        }
+    }
+
+    /// Returns the ENS name of `symbol`, failing if the symbol isn't made
+    /// of non-empty labels of supported characters.
+    pub fn name(&self, symbol: &str) -> Result<String, Error> {
+        let valid = symbol.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || b"-_$".contains(&byte))
+        });
+
+        if !valid {
+            return Err(Error::new(
+                TokenId::Symbol(symbol.to_string()),
+                InternalError::InvalidEnsName(format!("{symbol}.{}", self.parent)),
+            ));
+        }
+
+        Ok(format!("{}.{}", symbol.to_ascii_lowercase(), self.parent))
+    }
+}
+
+#[async_trait]
+impl<P, N> SymbolResolver for EnsResolver<P, N>
+where
+    P: Provider<N> + Send + Sync,
+    N: Network,
+{
+    async fn resolve(&self, chain_id: u64, symbol: &str) -> Result<Vec<Address>, Error> {
+        let provider_chain_id = self
+            .provider
+            .get_chain_id()
+            .await
+            .map_err(|err| Error::new(TokenId::Symbol(symbol.to_string()), err))?;
+
+        if provider_chain_id != chain_id {
+            return Ok(Vec::new());
+        }
+
+        let node = namehash(&self.name(symbol)?);
+        let registry = IEnsRegistry::new(ENS_REGISTRY_ADDRESS, &self.provider);
+
+        let resolver = registry
+            .resolver(node)
+            .call()
+            .await
+            .map_err(|err| Error::new(TokenId::Symbol(symbol.to_string()), err))?;
+
+        if resolver.is_zero() {
+            return Ok(Vec::new());
+        }
+
+        let address = IEnsResolver::new(resolver, &self.provider)
+            .addr(node)
+            .call()
+            .await
+            .map_err(|err| Error::new(TokenId::Symbol(symbol.to_string()), err))?;
+
+        Ok(if address.is_zero() {
+            Vec::new()
+        } else {
+            vec![address]
+        })
+    }
+}
+
+/// Computes the [ENS namehash] of a name.
+///
+/// [ENS namehash]: https://docs.ens.domains/resolution/names#namehash
+fn namehash(name: &str) -> B256 {
+    name.rsplit('.')
+        .filter(|label| !label.is_empty())
+        .fold(B256::ZERO, |node, label| {
+            keccak256([node.as_slice(), keccak256(label).as_slice()].concat())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{b256, Address, B256},
+        providers::ProviderBuilder,
+        transports::mock::Asserter,
+    };
+
+    use super::{namehash, EnsResolver, SymbolResolver};
+    use crate::{
+        mainnet::{USDC, WETH},
+        InternalError,
+    };
+
+    #[test]
+    fn test_namehash() {
+        assert_eq!(namehash(""), B256::ZERO);
+        assert_eq!(
+            namehash("eth"),
+            b256!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            b256!("de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+        );
+    }
+
+    #[test]
+    fn test_ens_name() {
+        let provider = ProviderBuilder::new().connect_mocked_client(Asserter::new());
+        let resolver = EnsResolver::new(provider, "tkn.eth");
+
+        assert_eq!(resolver.name("USDC").unwrap(), "usdc.tkn.eth");
+        assert_eq!(resolver.name("USDC.e").unwrap(), "usdc.e.tkn.eth");
+        assert_eq!(resolver.name("$ZRO").unwrap(), "$zro.tkn.eth");
+
+        for symbol in ["", "USDC.", "USD₮", "ＵＳＤＣ", "US DC"] {
+            assert!(
+                matches!(
+                    resolver.name(symbol).unwrap_err().source,
+                    InternalError::InvalidEnsName(_)
+                ),
+                "{symbol}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_callback_resolver() {
+        let resolver = |chain_id: u64, symbol: &str| -> Vec<Address> {
+            match (chain_id, symbol) {
+                (1, "WETH") => vec![WETH.address],
+                (1, "USD") => vec![USDC.address, WETH.address],
+                _ => Vec::new(),
+            }
+        };
+
+        assert_eq!(resolver.resolve(1, "WETH").await.unwrap(), [WETH.address]);
+        assert_eq!(resolver.resolve(1, "USD").await.unwrap().len(), 2);
+        assert!(resolver.resolve(10, "WETH").await.unwrap().is_empty());
+    }
+}
@@ -2,21 +2,91 @@ use std::collections::HashMap;
 
 use alloy::primitives::Address;
 
-use crate::{token_id::TokenId, Token};
+use crate::{error::InternalError, token_id::TokenId, Error, Token};
 
-use super::TokenStore;
+use super::{StoreSnapshot, TokenStore};
+
+/// What to do when inserting a token whose symbol is already used by
+/// another token of the same chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ConflictPolicy {
+    /// Keep both tokens, looking the symbol up to the token inserted first.
+    #[default]
+    PreferExisting,
+    /// Keep both tokens, looking the symbol up to the token inserted last.
+    PreferNew,
+    /// Keep the existing token only.
+    Reject,
+}
 
 /// A basic [`TokenStore`] implementation.
+///
+/// Tokens are keyed by address, and many tokens can share a symbol on the
+/// same chain: looking a symbol up returns the preferred one, according to
+/// the store [`ConflictPolicy`] or [`BasicTokenStore::prefer`], and
+/// [`TokenStore::candidates`] returns all of them.
 #[derive(Debug, Default, Clone)]
 pub struct BasicTokenStore {
-    tokens: HashMap<(u64, TokenId), Token>,
+    tokens: HashMap<(u64, Address), Token>,
+    symbols: HashMap<(u64, String), Vec<Address>>,
+    policy: ConflictPolicy,
 }
 
 impl BasicTokenStore {
     /// Creates a new [`BasicTokenStore`]
     pub fn new() -> Self {
+        Self::with_conflict_policy(ConflictPolicy::default())
+    }
+
+    /// Creates a new [`BasicTokenStore`] resolving symbol conflicts with
+    /// `policy`.
+    pub fn with_conflict_policy(policy: ConflictPolicy) -> Self {
         Self {
             tokens: HashMap::new(),
+            symbols: HashMap::new(),
+            policy,
+        }
+    }
+
+    /// Returns the store conflict policy.
+    pub const fn conflict_policy(&self) -> ConflictPolicy {
+        self.policy
+    }
+
+    /// Makes the token at `address` the one its symbol is looked up to,
+    /// like a token verified against a trusted list.
+    ///
+    /// Returns `false` if the token isn't in the store.
+    pub fn prefer(&mut self, chain_id: u64, address: Address) -> bool {
+        let Some(token) = self.tokens.get(&(chain_id, address)) else {
+            return false;
+        };
+
+        if let Some(candidates) = self.symbols.get_mut(&(chain_id, token.symbol.clone())) {
+            candidates.retain(|candidate| *candidate != address);
+            candidates.insert(0, address);
+        }
+
+        true
+    }
+
+    fn remove_candidate(&mut self, key: &(u64, String), address: Address) {
+        if let Some(candidates) = self.symbols.get_mut(key) {
+            candidates.retain(|candidate| *candidate != address);
+
+            if candidates.is_empty() {
+                self.symbols.remove(key);
+            }
+        }
+    }
+
+    fn address(&self, chain_id: u64, id: TokenId) -> Option<Address> {
+        match id {
+            TokenId::Address(address) => Some(address),
+            TokenId::Symbol(symbol) => self
+                .symbols
+                .get(&(chain_id, symbol))
+                .and_then(|candidates| candidates.first().copied()),
         }
     }
 }
@@ -25,45 +95,192 @@ impl<'a> TokenStore<'a> for BasicTokenStore {
     type Item = &'a Token;
 
     fn get(&'a self, chain_id: u64, id: TokenId) -> Option<Self::Item> {
-        self.tokens.get(&(chain_id, id.clone()))
+        let address = self.address(chain_id, id)?;
+        self.tokens.get(&(chain_id, address))
     }
 
     fn get_mut(&mut self, chain_id: u64, id: TokenId) -> Option<&mut Token> {
-        self.tokens.get_mut(&(chain_id, id.clone()))
+        let address = self.address(chain_id, id)?;
+        self.tokens.get_mut(&(chain_id, address))
     }
 
+    /// Inserts a token into the store.
+    ///
+    /// With [`ConflictPolicy::Reject`], a token whose symbol is used by
+    /// another token is ignored, see [`TokenStore::try_insert`].
     fn insert(&mut self, chain_id: u64, token: Token) {
-        self.tokens
-            .insert((chain_id, TokenId::Address(token.address)), token.clone());
-        self.tokens
-            .insert((chain_id, TokenId::Symbol(token.symbol.to_string())), token);
+        let _ = self.try_insert(chain_id, token);
+    }
+
+    /// Inserts a token into the store, failing with
+    /// [`InternalError::SymbolConflict`] if its symbol is used by another
+    /// token and the store policy is [`ConflictPolicy::Reject`].
+    fn try_insert(&mut self, chain_id: u64, token: Token) -> Result<(), Error> {
+        let symbol_key = (chain_id, token.symbol.clone());
+
+        if self.policy == ConflictPolicy::Reject {
+            if let Some(existing) = self
+                .symbols
+                .get(&symbol_key)
+                .and_then(|candidates| candidates.iter().find(|a| **a != token.address))
+            {
+                return Err(Error::new(
+                    TokenId::Symbol(token.symbol),
+                    InternalError::SymbolConflict(*existing),
+                ));
+            }
+        }
+
+        if let Some(previous) = self.tokens.get(&(chain_id, token.address)) {
+            if previous.symbol != token.symbol {
+                let previous_key = (chain_id, previous.symbol.clone());
+                self.remove_candidate(&previous_key, token.address);
+            }
+        }
+
+        let candidates = self.symbols.entry(symbol_key).or_default();
+
+        if !candidates.contains(&token.address) {
+            match self.policy {
+                ConflictPolicy::PreferNew => candidates.insert(0, token.address),
+                ConflictPolicy::PreferExisting | ConflictPolicy::Reject => {
+                    candidates.push(token.address)
+                }
+            }
+        }
+
+        self.tokens.insert((chain_id, token.address), token);
+
+        Ok(())
     }
 
     fn contains(&self, chain_id: u64, id: TokenId) -> bool {
-        self.tokens.contains_key(&(chain_id, id))
+        self.address(chain_id, id)
+            .is_some_and(|address| self.tokens.contains_key(&(chain_id, address)))
     }
 
     fn symbols(&'a self, chain_id: Option<u64>) -> Vec<String> {
-        self.tokens
+        self.symbols
             .keys()
-            .filter_map(move |(token_chain_id, id)| match (id, chain_id) {
-                (TokenId::Symbol(id), Some(chain_id)) if token_chain_id == &chain_id => {
-                    Some(id.clone())
-                }
-                (TokenId::Symbol(id), None) => Some(id.clone()),
-                _ => None,
-            })
+            .filter(|(token_chain_id, _)| chain_id.is_none_or(|id| id == *token_chain_id))
+            .map(|(_, symbol)| symbol.clone())
             .collect()
     }
 
     fn addresses(&'a self, chain_id: Option<u64>) -> Vec<Address> {
         self.tokens
             .keys()
-            .filter_map(move |(token_chain_id, id)| match (id, chain_id) {
-                (TokenId::Address(id), Some(chain_id)) if token_chain_id == &chain_id => Some(*id),
-                (TokenId::Address(id), None) => Some(*id),
-                _ => None,
-            })
+            .filter(|(token_chain_id, _)| chain_id.is_none_or(|id| id == *token_chain_id))
+            .map(|(_, address)| *address)
+            .collect()
+    }
+
+    fn candidates(&'a self, chain_id: u64, symbol: &str) -> Vec<Self::Item> {
+        self.symbols
+            .get(&(chain_id, symbol.to_string()))
+            .into_iter()
+            .flatten()
+            .filter_map(|address| self.tokens.get(&(chain_id, *address)))
             .collect()
     }
 }
+
+impl From<&BasicTokenStore> for StoreSnapshot {
+    fn from(store: &BasicTokenStore) -> Self {
+        let mut snapshot = StoreSnapshot::new();
+
+        for ((chain_id, _), token) in &store.tokens {
+            snapshot.insert(*chain_id, token.clone());
+        }
+
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::{BasicTokenStore, ConflictPolicy};
+    use crate::{mainnet::USDC, stores::Entry, InternalError, Token, TokenId, TokenStore};
+
+    fn usdc_clone() -> Token {
+        Token::new(
+            address!("1111111111111111111111111111111111111111"),
+            "USDC".to_string(),
+            18,
+        )
+    }
+
+    fn usdc_symbol() -> TokenId {
+        TokenId::Symbol("USDC".to_string())
+    }
+
+    #[test]
+    fn test_duplicate_symbols() {
+        let mut store = BasicTokenStore::new();
+        store.insert(1, USDC.clone());
+        store.insert(1, usdc_clone());
+
+        assert_eq!(store.get(1, usdc_symbol()).unwrap().address, USDC.address);
+        assert_eq!(
+            store.get(1, usdc_clone().address.into()).unwrap().decimals,
+            18
+        );
+        assert_eq!(store.candidates(1, "USDC").len(), 2);
+        assert_eq!(store.symbols(Some(1)), ["USDC"]);
+        assert_eq!(store.addresses(Some(1)).len(), 2);
+
+        assert!(store.prefer(1, usdc_clone().address));
+        assert_eq!(store.get(1, usdc_symbol()).unwrap().decimals, 18);
+        assert_eq!(store.candidates(1, "USDC")[1].address, USDC.address);
+    }
+
+    #[test]
+    fn test_conflict_policies() {
+        let mut store = BasicTokenStore::with_conflict_policy(ConflictPolicy::PreferNew);
+        store.insert(1, USDC.clone());
+        store.insert(1, usdc_clone());
+        assert_eq!(store.get(1, usdc_symbol()).unwrap().decimals, 18);
+
+        let mut store = BasicTokenStore::with_conflict_policy(ConflictPolicy::Reject);
+        store.insert(1, USDC.clone());
+        let err = store.try_insert(1, usdc_clone()).unwrap_err();
+
+        assert!(matches!(err.source, InternalError::SymbolConflict(a) if a == USDC.address));
+        assert!(!store.contains(1, usdc_clone().address.into()));
+        assert!(store.try_insert(2, usdc_clone()).is_ok());
+        assert!(store.try_insert(1, USDC.clone()).is_ok());
+    }
+
+    #[test]
+    fn test_rejected_entry() {
+        let mut store = BasicTokenStore::with_conflict_policy(ConflictPolicy::Reject);
+        store.insert(1, USDC.clone());
+
+        let Entry::Vacant(entry) = store.entry(1, usdc_clone().address.into()) else {
+            panic!("entry should be vacant");
+        };
+        let err = entry.insert(usdc_clone()).unwrap_err();
+
+        assert!(matches!(err.source, InternalError::SymbolConflict(a) if a == USDC.address));
+        assert!(!store.contains(1, usdc_clone().address.into()));
+    }
+
+    #[test]
+    fn test_symbol_change() {
+        let mut store = BasicTokenStore::new();
+        store.insert(1, USDC.clone());
+        store.insert(1, Token::new(USDC.address, "USDC.e".to_string(), 6));
+
+        assert!(!store.contains(1, usdc_symbol()));
+        assert_eq!(
+            store
+                .get(1, TokenId::Symbol("USDC.e".to_string()))
+                .unwrap()
+                .address,
+            USDC.address
+        );
+        assert_eq!(store.addresses(Some(1)).len(), 1);
+    }
+}
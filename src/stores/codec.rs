@@ -0,0 +1,241 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Write},
+};
+
+use alloy::primitives::Address;
+
+use crate::Token;
+
+/// The magic bytes starting an encoded snapshot.
+const MAGIC: &[u8; 4] = b"E20S";
+
+/// The current encoding version.
+const VERSION: u8 = 1;
+
+/// Encodes tokens partitioned by chain, as a snapshot.
+///
+/// Integers are encoded as unsigned LEB128 varints, and strings as their
+/// varint length followed by their UTF-8 bytes.
+pub(crate) fn encode_chains<W: Write>(
+    chains: &BTreeMap<u64, Vec<Token>>,
+    writer: &mut W,
+) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION])?;
+    write_varint(writer, chains.len() as u64)?;
+
+    for (chain_id, tokens) in chains {
+        write_varint(writer, *chain_id)?;
+        write_varint(writer, tokens.len() as u64)?;
+
+        for token in tokens {
+            encode_token(token, writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Decodes tokens partitioned by chain, from a snapshot.
+pub(crate) fn decode_chains<R: Read>(reader: &mut R) -> io::Result<BTreeMap<u64, Vec<Token>>> {
+    let mut header = [0; 5];
+    reader.read_exact(&mut header)?;
+
+    if &header[..4] != MAGIC {
+        return Err(invalid_data("not a token store snapshot"));
+    }
+
+    if header[4] != VERSION {
+        return Err(invalid_data("unsupported token store snapshot version"));
+    }
+
+    let mut chains = BTreeMap::new();
+
+    for _ in 0..read_varint(reader)? {
+        let chain_id = read_varint(reader)?;
+        let count = read_varint(reader)?;
+        // Bounded, as the count isn't trusted
+        let mut tokens = Vec::with_capacity(count.min(1024) as usize);
+
+        for _ in 0..count {
+            tokens.push(decode_token(reader)?);
+        }
+
+        chains.insert(chain_id, tokens);
+    }
+
+    Ok(chains)
+}
+
+/// Encodes a single token.
+pub(crate) fn encode_token<W: Write>(token: &Token, writer: &mut W) -> io::Result<()> {
+    writer.write_all(token.address.as_slice())?;
+    writer.write_all(&[token.decimals])?;
+    write_bytes(writer, token.symbol.as_bytes())?;
+
+    match &token.name {
+        Some(name) => {
+            writer.write_all(&[1])?;
+            write_bytes(writer, name.as_bytes())
+        }
+        None => writer.write_all(&[0]),
+    }
+}
+
+/// Decodes a single token.
+pub(crate) fn decode_token<R: Read>(reader: &mut R) -> io::Result<Token> {
+    let mut address = Address::ZERO;
+    reader.read_exact(address.as_mut_slice())?;
+
+    let mut decimals = [0];
+    reader.read_exact(&mut decimals)?;
+
+    let token = Token::new(address, read_string(reader)?, decimals[0]);
+
+    let mut has_name = [0];
+    reader.read_exact(&mut has_name)?;
+
+    match has_name[0] {
+        0 => Ok(token),
+        1 => Ok(token.with_name(read_string(reader)?)),
+        _ => Err(invalid_data("invalid token name flag")),
+    }
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut value = 0;
+
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+
+        // The last byte only has room for the most significant bit
+        if shift == 63 && byte[0] & 0x7f > 1 {
+            return Err(invalid_data("varint overflows u64"));
+        }
+
+        value |= ((byte[0] & 0x7f) as u64) << shift;
+
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(invalid_data("varint too long"))
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    write_varint(writer, bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+fn read_string<R: Read>(reader: &mut R) -> io::Result<String> {
+    let len = read_varint(reader)?;
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+
+    if bytes.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    String::from_utf8(bytes).map_err(|_| invalid_data("invalid UTF-8 string"))
+}
+
+pub(crate) fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        mainnet::{USDC, WETH},
+        Token,
+    };
+
+    use super::{
+        decode_chains, decode_token, encode_chains, encode_token, read_varint, write_varint,
+    };
+
+    #[test]
+    fn test_varint() {
+        for value in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut buf = Vec::new();
+            write_varint(&mut buf, value).unwrap();
+
+            assert_eq!(read_varint(&mut buf.as_slice()).unwrap(), value);
+        }
+
+        let overflow = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+        assert_eq!(
+            read_varint(&mut overflow.as_slice()).unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_token() {
+        let token = USDC.clone().with_name("USD Coin".to_string());
+        let mut buf = Vec::new();
+        encode_token(&token, &mut buf).unwrap();
+
+        let decoded = decode_token(&mut buf.as_slice()).unwrap();
+
+        assert_eq!(decoded.address, token.address);
+        assert_eq!(decoded.symbol, token.symbol);
+        assert_eq!(decoded.decimals, token.decimals);
+        assert_eq!(decoded.name, token.name);
+    }
+
+    #[test]
+    fn test_chains() {
+        let chains = BTreeMap::from([(1, vec![WETH.clone(), USDC.clone()]), (10, vec![])]);
+        let mut buf = Vec::new();
+        encode_chains(&chains, &mut buf).unwrap();
+
+        let decoded = decode_chains(&mut buf.as_slice()).unwrap();
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(
+            decoded[&1].iter().map(|t| t.address).collect::<Vec<_>>(),
+            vec![WETH.address, USDC.address]
+        );
+        assert!(decoded[&10].is_empty());
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(decode_chains(&mut b"nope!".as_slice()).is_err());
+
+        let mut buf = Vec::new();
+        encode_chains(&BTreeMap::from([(1, vec![WETH.clone()])]), &mut buf).unwrap();
+        buf.truncate(buf.len() - 1);
+
+        assert!(decode_chains(&mut buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_truncated_string() {
+        let token = Token::new(Default::default(), "A".repeat(200), 18);
+        let mut buf = Vec::new();
+        encode_token(&token, &mut buf).unwrap();
+        buf.truncate(30);
+
+        assert!(decode_token(&mut buf.as_slice()).is_err());
+    }
+}
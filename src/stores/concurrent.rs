@@ -0,0 +1,456 @@
+use std::{
+    collections::{
+        hash_map::{Entry, RandomState},
+        HashMap,
+    },
+    future::Future,
+    hash::{BuildHasher, Hash},
+    sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use alloy::primitives::Address;
+use async_once_cell::OnceCell;
+use async_trait::async_trait;
+
+use crate::{error::InternalError, Error, Token, TokenId};
+
+use super::{SharedTokenStore, TokenStore};
+
+/// The default number of shards.
+const DEFAULT_SHARDS: usize = 16;
+
+/// A shard of the tokens, keyed by chain and address, and of the symbol
+/// index, each key living in the shard its hash selects.
+#[derive(Debug, Default)]
+struct Shard {
+    tokens: HashMap<(u64, Address), Arc<Token>>,
+    symbols: HashMap<(u64, String), Address>,
+}
+
+/// The outcome of a fetch, shared by the callers awaiting it.
+type FetchCell = OnceCell<Result<Arc<Token>, Arc<Error>>>;
+
+/// The fetches in progress, by chain and address.
+type InFlight = Mutex<HashMap<(u64, Address), Flight>>;
+
+/// A fetch in progress, with the number of callers awaiting it.
+#[derive(Debug, Default)]
+struct Flight {
+    cell: Arc<FetchCell>,
+    callers: usize,
+}
+
+/// A caller of a fetch in progress, leaving it when dropped, even if the
+/// caller future is cancelled. The last caller to leave removes the fetch.
+struct FlightGuard<'a> {
+    in_flight: &'a InFlight,
+    key: (u64, Address),
+}
+
+impl FlightGuard<'_> {
+    fn lock(&self) -> MutexGuard<'_, HashMap<(u64, Address), Flight>> {
+        self.in_flight.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Drop for FlightGuard<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.lock();
+
+        if let Entry::Occupied(mut flight) = in_flight.entry(self.key) {
+            flight.get_mut().callers -= 1;
+
+            if flight.get().callers == 0 {
+                flight.remove();
+            }
+        }
+    }
+}
+
+/// A thread-safe [`TokenStore`] implementation, sharding its tokens over
+/// many locks to reduce contention.
+///
+/// It also implements [`SharedTokenStore`], deduplicating concurrent
+/// fetches of the same token, see [`Erc20ProviderExt::get_token_shared`].
+/// A failed fetch fails every caller awaiting it rather than being retried
+/// by each of them, see [`ConcurrentTokenStore::get_or_fetch`].
+///
+/// Tokens are stored once, keyed by address, and symbols are looked up
+/// through an index of their address. Each symbol resolves to the token
+/// last inserted with it, the previous one being only reachable by address,
+/// see [`TokenStore`].
+///
+/// [`Erc20ProviderExt::get_token_shared`]: crate::Erc20ProviderExt::get_token_shared
+#[derive(Debug)]
+pub struct ConcurrentTokenStore {
+    shards: Box<[RwLock<Shard>]>,
+    hasher: RandomState,
+    in_flight: InFlight,
+}
+
+impl ConcurrentTokenStore {
+    /// Creates a new [`ConcurrentTokenStore`].
+    pub fn new() -> Self {
+        Self::with_shards(DEFAULT_SHARDS)
+    }
+
+    /// Creates a new [`ConcurrentTokenStore`] with the given number of
+    /// shards, at least one.
+    pub fn with_shards(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1)).map(|_| RwLock::default()).collect(),
+            hasher: RandomState::new(),
+            in_flight: Mutex::default(),
+        }
+    }
+
+    fn index<K: Hash>(&self, key: &K) -> usize {
+        self.hasher.hash_one(key) as usize % self.shards.len()
+    }
+
+    fn read<K: Hash>(&self, key: &K) -> RwLockReadGuard<'_, Shard> {
+        self.shards[self.index(key)]
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn write<K: Hash>(&self, key: &K) -> RwLockWriteGuard<'_, Shard> {
+        self.shards[self.index(key)]
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn shard_mut<K: Hash>(&mut self, key: &K) -> &mut Shard {
+        let index = self.index(key);
+
+        self.shards[index]
+            .get_mut()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns the address of the token with the given id.
+    fn address(&self, chain_id: u64, id: &TokenId) -> Option<Address> {
+        match id {
+            TokenId::Address(address) => Some(*address),
+            TokenId::Symbol(symbol) => {
+                let key = (chain_id, symbol.clone());
+                self.read(&key).symbols.get(&key).copied()
+            }
+        }
+    }
+
+    fn collect<T, F>(&self, f: F) -> Vec<T>
+    where
+        F: Fn(&Shard) -> Vec<T>,
+    {
+        self.shards
+            .iter()
+            .flat_map(|shard| f(&shard.read().unwrap_or_else(|err| err.into_inner())))
+            .collect()
+    }
+}
+
+impl Default for ConcurrentTokenStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SharedTokenStore for ConcurrentTokenStore {
+    fn get(&self, chain_id: u64, id: &TokenId) -> Option<Arc<Token>> {
+        let key = (chain_id, self.address(chain_id, id)?);
+
+        self.read(&key).tokens.get(&key).cloned()
+    }
+
+    /// Inserts a token, then indexes its symbol.
+    ///
+    /// A concurrent lookup by symbol may return the token previously
+    /// indexed with it until the symbol is indexed.
+    fn insert(&self, chain_id: u64, token: Token) -> Arc<Token> {
+        let token = Arc::new(token);
+        let key = (chain_id, token.address);
+
+        let previous = self.write(&key).tokens.insert(key, token.clone());
+
+        let symbol_key = (chain_id, token.symbol.clone());
+        self.write(&symbol_key)
+            .symbols
+            .insert(symbol_key, token.address);
+
+        // The previous symbol must not resolve to this token anymore
+        if let Some(previous) = previous.filter(|previous| previous.symbol != token.symbol) {
+            let previous_key = (chain_id, previous.symbol.clone());
+            let mut shard = self.write(&previous_key);
+
+            if shard.symbols.get(&previous_key) == Some(&token.address) {
+                shard.symbols.remove(&previous_key);
+            }
+        }
+
+        token
+    }
+
+    /// Returns the token at `address`, or fetches it with `fetch` and
+    /// inserts it into the store, concurrent misses awaiting a single
+    /// `fetch`.
+    ///
+    /// If the fetch fails, every caller awaiting it fails with its error,
+    /// wrapped in [`InternalError::FetchFailed`] unless a single caller
+    /// awaited it. The next call fetches the token again. If the caller
+    /// running `fetch` is cancelled, another caller awaiting it runs its
+    /// own `fetch`.
+    async fn get_or_fetch<F>(
+        &self,
+        chain_id: u64,
+        address: Address,
+        fetch: F,
+    ) -> Result<Arc<Token>, Error>
+    where
+        F: Future<Output = Result<Token, Error>> + Send,
+    {
+        if let Some(token) = SharedTokenStore::get(self, chain_id, &address.into()) {
+            return Ok(token);
+        }
+
+        let guard = FlightGuard {
+            in_flight: &self.in_flight,
+            key: (chain_id, address),
+        };
+        let cell = {
+            let mut in_flight = guard.lock();
+            let flight = in_flight.entry(guard.key).or_default();
+            flight.callers += 1;
+
+            flight.cell.clone()
+        };
+
+        let result = cell
+            .get_or_init(async {
+                // Fetched by a call that completed before this cell existed
+                if let Some(token) = SharedTokenStore::get(self, chain_id, &address.into()) {
+                    return Ok(token);
+                }
+
+                match fetch.await {
+                    Ok(token) => Ok(SharedTokenStore::insert(self, chain_id, token)),
+                    Err(err) => Err(Arc::new(err)),
+                }
+            })
+            .await
+            .clone();
+
+        // Leaving the fetch drops its cell if this is the last caller, for
+        // the error to be returned as is if no other caller holds it
+        drop(guard);
+        drop(cell);
+
+        result.map_err(|err| {
+            Arc::try_unwrap(err)
+                .unwrap_or_else(|err| Error::new(address.into(), InternalError::FetchFailed(err)))
+        })
+    }
+}
+
+impl<'a> TokenStore<'a> for ConcurrentTokenStore {
+    type Item = Arc<Token>;
+
+    fn get(&'a self, chain_id: u64, id: TokenId) -> Option<Self::Item> {
+        SharedTokenStore::get(self, chain_id, &id)
+    }
+
+    /// Returns the mutable token with the given id.
+    ///
+    /// The token is cloned if handed out by [`TokenStore::get`] or
+    /// [`SharedTokenStore`] methods, for the mutation to only affect the
+    /// store.
+    fn get_mut(&mut self, chain_id: u64, id: TokenId) -> Option<&mut Token> {
+        let key = (chain_id, self.address(chain_id, &id)?);
+
+        self.shard_mut(&key).tokens.get_mut(&key).map(Arc::make_mut)
+    }
+
+    fn insert(&mut self, chain_id: u64, token: Token) {
+        SharedTokenStore::insert(self, chain_id, token);
+    }
+
+    fn contains(&self, chain_id: u64, id: TokenId) -> bool {
+        SharedTokenStore::get(self, chain_id, &id).is_some()
+    }
+
+    fn symbols(&'a self, chain_id: Option<u64>) -> Vec<String> {
+        self.collect(|shard| {
+            shard
+                .symbols
+                .keys()
+                .filter(|(token_chain_id, _)| chain_id.is_none_or(|c| c == *token_chain_id))
+                .map(|(_, symbol)| symbol.clone())
+                .collect()
+        })
+    }
+
+    fn addresses(&'a self, chain_id: Option<u64>) -> Vec<Address> {
+        self.collect(|shard| {
+            shard
+                .tokens
+                .keys()
+                .filter(|(token_chain_id, _)| chain_id.is_none_or(|c| c == *token_chain_id))
+                .map(|(_, address)| *address)
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use futures::future::join_all;
+
+    use crate::{
+        mainnet::{USDC, WETH},
+        Error, InternalError, SharedTokenStore, Token, TokenId, TokenStore,
+    };
+
+    use super::ConcurrentTokenStore;
+
+    #[test]
+    fn test_token_store() {
+        let mut store = ConcurrentTokenStore::with_shards(4);
+
+        TokenStore::insert(&mut store, 1, WETH.clone());
+        TokenStore::insert(&mut store, 1, USDC.clone());
+
+        assert!(store.contains(1, TokenId::Symbol("USDC".to_string())));
+        assert!(!store.contains(10, USDC.address.into()));
+        assert_eq!(store.addresses(Some(1)).len(), 2);
+        assert_eq!(store.symbols(None).len(), 2);
+
+        store.get_mut(1, WETH.address.into()).unwrap().decimals = 8;
+        assert_eq!(
+            TokenStore::get(&store, 1, WETH.address.into())
+                .unwrap()
+                .decimals,
+            8
+        );
+    }
+
+    #[test]
+    fn test_get_mut_by_symbol() {
+        let mut store = ConcurrentTokenStore::new();
+        TokenStore::insert(&mut store, 1, USDC.clone());
+        let shared = TokenStore::get(&store, 1, USDC.address.into()).unwrap();
+
+        store
+            .get_mut(1, TokenId::Symbol("USDC".to_string()))
+            .unwrap()
+            .decimals = 18;
+
+        assert_eq!(
+            TokenStore::get(&store, 1, USDC.address.into())
+                .unwrap()
+                .decimals,
+            18
+        );
+        assert_eq!(shared.decimals, 6);
+    }
+
+    #[test]
+    fn test_symbol_change() {
+        let mut store = ConcurrentTokenStore::new();
+        TokenStore::insert(&mut store, 1, USDC.clone());
+        TokenStore::insert(
+            &mut store,
+            1,
+            Token::new(USDC.address, "USDC.e".to_string(), 6),
+        );
+
+        assert!(!store.contains(1, TokenId::Symbol("USDC".to_string())));
+        assert_eq!(store.symbols(Some(1)), ["USDC.e"]);
+        assert_eq!(store.addresses(Some(1)).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_single_flight() {
+        let store = ConcurrentTokenStore::new();
+        let fetches = AtomicUsize::new(0);
+
+        let tokens = join_all((0..10).map(|_| {
+            store.get_or_fetch(1, USDC.address, async {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                Ok(USDC.clone())
+            })
+        }))
+        .await;
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(tokens
+            .iter()
+            .all(|token| Arc::ptr_eq(token.as_ref().unwrap(), tokens[0].as_ref().unwrap())));
+        assert!(store.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shared_error() {
+        let store = ConcurrentTokenStore::new();
+        let fetches = AtomicUsize::new(0);
+
+        let results = join_all((0..10).map(|_| {
+            store.get_or_fetch(1, USDC.address, async {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                Err(Error::new(
+                    USDC.address.into(),
+                    InternalError::NotInStore("USDC".to_string()),
+                ))
+            })
+        }))
+        .await;
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(results.iter().all(|result| matches!(
+            &result.as_ref().unwrap_err().source,
+            InternalError::FetchFailed(_) | InternalError::NotInStore(_)
+        )));
+        assert!(store.in_flight.lock().unwrap().is_empty());
+
+        // A single caller gets the error as is, and the next call refetches
+        let result = store
+            .get_or_fetch(1, USDC.address, async {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                Err(Error::new(
+                    USDC.address.into(),
+                    InternalError::NotInStore("USDC".to_string()),
+                ))
+            })
+            .await;
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+        assert!(matches!(
+            result.unwrap_err().source,
+            InternalError::NotInStore(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_fetch() {
+        let store = ConcurrentTokenStore::new();
+
+        let fetch = store.get_or_fetch(1, USDC.address, std::future::pending());
+        assert!(futures::poll!(Box::pin(fetch)).is_pending());
+        assert!(store.in_flight.lock().unwrap().is_empty());
+
+        let token = store
+            .get_or_fetch(1, USDC.address, async { Ok(USDC.clone()) })
+            .await
+            .unwrap();
+
+        assert_eq!(*token, *USDC);
+        assert!(store.in_flight.lock().unwrap().is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Token, TokenId};
+use crate::{error::InternalError, Error, Token, TokenId};
 
 use super::TokenStore;
 
@@ -110,8 +110,19 @@ where
     /// # Returns
     ///
     /// A mutable reference to the newly inserted token
-    pub fn insert(self, token: Token) -> &'a mut Token {
-        self.store.insert(self.chain_id, token);
-        self.store.get_mut(self.chain_id, self.id.clone()).unwrap()
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store rejects the token, see
+    /// [`TokenStore::try_insert`].
+    pub fn insert(self, token: Token) -> Result<&'a mut Token, Error> {
+        let address = token.address;
+        self.store.try_insert(self.chain_id, token)?;
+
+        // Looked up by address, as the token symbol may differ from the
+        // entry one
+        self.store
+            .get_mut(self.chain_id, address.into())
+            .ok_or_else(|| Error::new(self.id, InternalError::NotInStore(address.to_string())))
     }
 }
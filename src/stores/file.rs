@@ -0,0 +1,745 @@
+use std::{
+    ffi::OsString,
+    fs::{File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use alloy::primitives::Address;
+
+use crate::{Error, Token, TokenId};
+
+use super::{
+    codec,
+    file_index::{FileIndex, IndexKey, KeyKind},
+    TokenStore,
+};
+
+/// A [`TokenStore`] implementation backed by an append-only log file.
+///
+/// Tokens are looked up through an on-disk hash index, stored next to the
+/// log with an `.idx` suffix, so that nothing but file handles is kept in
+/// memory. Inserting a token appends it to the log, superseding any
+/// previous record with the same address, and
+/// [`FileTokenStore::compact`] rewrites the log without the superseded
+/// records.
+///
+/// The index is rebuilt from the log if it is missing or invalid, and
+/// catches up with records appended since it was last updated.
+///
+/// Changes made through [`TokenStore::get_mut`] are not persisted: insert
+/// the token again to persist them.
+///
+/// Each symbol resolves to the token last inserted with it, the previous
+/// one being only reachable by address, see [`TokenStore`].
+#[derive(Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+    file: Mutex<File>,
+    index: Mutex<FileIndex>,
+    len: u64,
+    scratch: Option<Token>,
+}
+
+impl FileTokenStore {
+    /// Opens the store at `path`, creating it if needed.
+    ///
+    /// A record truncated by an interrupted write at the end of the log is
+    /// discarded, while any other invalid record fails with
+    /// [`io::ErrorKind::InvalidData`].
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+
+        let index_path = index_path(&path);
+        let mut index = match FileIndex::open(&index_path)? {
+            Some(index) if index.log_len() <= file.metadata()?.len() => index,
+            _ => FileIndex::create(&index_path)?,
+        };
+
+        // Only the records appended since the index was last updated are
+        // read, with their own handle as the index reads from the log
+        let mut reader = BufReader::new(File::open(&path)?);
+        reader.seek(SeekFrom::Start(index.log_len()))?;
+        let mut len = index.log_len();
+
+        loop {
+            match read_record(&mut reader) {
+                Ok((chain_id, token, size)) => {
+                    insert_keys(&mut index, chain_id, &token, len)?;
+                    len += size;
+                }
+                // Only a record running past the end of the log is torn
+                Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        if file.metadata()?.len() != len {
+            file.set_len(len)?;
+        }
+        if index.log_len() != len {
+            index.set_log_len(len)?;
+        }
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+            index: Mutex::new(index),
+            len,
+            scratch: None,
+        })
+    }
+
+    /// Returns the path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Flushes the log and index files to disk.
+    pub fn sync(&self) -> io::Result<()> {
+        self.lock().sync_all()?;
+        self.index().sync()
+    }
+
+    /// Rewrites the log file without the superseded records.
+    ///
+    /// The log and its index are written to temporary files first, removed
+    /// if compacting fails, then renamed over the current ones.
+    pub fn compact(&mut self) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("compact");
+        let tmp_index_path = index_path(&tmp_path);
+
+        let result = self.write_compacted(&tmp_path, &tmp_index_path);
+
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+            let _ = std::fs::remove_file(&tmp_index_path);
+        }
+
+        let (index, len) = result?;
+
+        *self.lock() = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+        *self.index() = index;
+        self.len = len;
+
+        Ok(())
+    }
+
+    /// Writes the current records to `tmp_path`, then renames it and its
+    /// index over the current log and index.
+    fn write_compacted(
+        &self,
+        tmp_path: &Path,
+        tmp_index_path: &Path,
+    ) -> io::Result<(FileIndex, u64)> {
+        let mut tmp = BufWriter::new(File::create(tmp_path)?);
+        let mut index = FileIndex::create(tmp_index_path)?;
+        let mut len = 0;
+
+        let mut offsets = self.index().offsets(KeyKind::Address)?;
+        offsets.sort_unstable();
+
+        for offset in offsets {
+            let (chain_id, token) = self.read_at(offset)?;
+            let record = encode_record(chain_id, &token)?;
+
+            tmp.write_all(&record)?;
+            insert_keys(&mut index, chain_id, &token, len)?;
+            len += record.len() as u64;
+        }
+
+        tmp.into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+        index.set_log_len(len)?;
+        index.sync()?;
+
+        // The current index is removed first: if interrupted, reopening the
+        // store rebuilds the index rather than using a stale one
+        let index_path = index_path(&self.path);
+        match std::fs::remove_file(&index_path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        std::fs::rename(tmp_path, &self.path)?;
+        index.rename(&index_path)?;
+        sync_parent(&self.path)?;
+
+        Ok((index, len))
+    }
+
+    fn append(&mut self, chain_id: u64, token: &Token) -> io::Result<()> {
+        let record = encode_record(chain_id, token)?;
+        let offset = self.len;
+
+        {
+            let mut file = self.lock();
+
+            if let Err(err) = file.write_all(&record) {
+                // Drop any partial record, for the next one to start at `len`
+                let _ = file.set_len(offset);
+                return Err(err);
+            }
+        }
+
+        // The record is in the log from now on: if the index fails to
+        // update, it catches up when the store is reopened
+        self.len += record.len() as u64;
+
+        let mut index = self.index();
+        insert_keys(&mut index, chain_id, token, offset)?;
+        index.set_log_len(self.len)
+    }
+
+    /// Returns the current record of `id`, with its offset.
+    fn lookup(&self, chain_id: u64, id: &TokenId) -> io::Result<Option<(u64, Token)>> {
+        let Some(offset) = self.offset(&IndexKey::new(chain_id, id))? else {
+            return Ok(None);
+        };
+
+        let (record_chain_id, token) = self.read_at(offset)?;
+        let matches = record_chain_id == chain_id
+            && match id {
+                TokenId::Address(address) => token.address == *address,
+                TokenId::Symbol(symbol) => {
+                    token.symbol == *symbol && self.is_current(chain_id, &token, offset)?
+                }
+            };
+
+        Ok(matches.then_some((offset, token)))
+    }
+
+    /// Returns `true` if the record of `token` at `offset` isn't
+    /// superseded.
+    fn is_current(&self, chain_id: u64, token: &Token, offset: u64) -> io::Result<bool> {
+        Ok(self.offset(&IndexKey::address(chain_id, token.address))? == Some(offset))
+    }
+
+    /// Returns the current records of the index keys of the given `kind`.
+    fn records(&self, kind: KeyKind) -> io::Result<Vec<(u64, Token)>> {
+        let offsets = self.index().offsets(kind)?;
+        let mut records = Vec::with_capacity(offsets.len());
+
+        for offset in offsets {
+            let (chain_id, token) = self.read_at(offset)?;
+
+            if kind == KeyKind::Address || self.is_current(chain_id, &token, offset)? {
+                records.push((chain_id, token));
+            }
+        }
+
+        Ok(records)
+    }
+
+    fn offset(&self, key: &IndexKey) -> io::Result<Option<u64>> {
+        self.index().get(key)
+    }
+
+    fn read_at(&self, offset: u64) -> io::Result<(u64, Token)> {
+        if offset >= self.len {
+            return Err(codec::invalid_data("record offset past the end of the log"));
+        }
+
+        let mut file = self.lock();
+        file.seek(SeekFrom::Start(offset))?;
+
+        read_record(&mut BufReader::new(&mut *file)).map(|(chain_id, token, _)| (chain_id, token))
+    }
+
+    /// Reads the log once, returning the records matching `filter` with
+    /// their offset.
+    fn scan<F>(&self, filter: F) -> io::Result<Vec<(u64, Token)>>
+    where
+        F: Fn(u64, &Token) -> bool,
+    {
+        let mut file = self.lock();
+        file.seek(SeekFrom::Start(0))?;
+
+        let mut reader = BufReader::new(&mut *file);
+        let mut records = Vec::new();
+        let mut offset = 0;
+
+        while offset < self.len {
+            let (chain_id, token, size) = read_record(&mut reader)?;
+
+            if filter(chain_id, &token) {
+                records.push((offset, token));
+            }
+            offset += size;
+        }
+
+        Ok(records)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, File> {
+        self.file.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn index(&self) -> MutexGuard<'_, FileIndex> {
+        self.index.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<'a> TokenStore<'a> for FileTokenStore {
+    type Item = Box<Token>;
+
+    fn get(&'a self, chain_id: u64, id: TokenId) -> Option<Self::Item> {
+        let (_, token) = self.lookup(chain_id, &id).ok()??;
+
+        Some(Box::new(token))
+    }
+
+    fn get_mut(&mut self, chain_id: u64, id: TokenId) -> Option<&mut Token> {
+        let (_, token) = self.lookup(chain_id, &id).ok()??;
+
+        Some(self.scratch.insert(token))
+    }
+
+    /// Appends a token to the log.
+    ///
+    /// The token is ignored if writing to the log file fails, see
+    /// [`TokenStore::try_insert`].
+    fn insert(&mut self, chain_id: u64, token: Token) {
+        let _ = self.try_insert(chain_id, token);
+    }
+
+    /// Appends a token to the log, failing with [`InternalError::Io`] if
+    /// writing to the log file fails, in which case the store is left
+    /// unchanged, or if updating the index fails, in which case the token
+    /// is indexed when the store is reopened.
+    ///
+    /// [`InternalError::Io`]: crate::InternalError::Io
+    fn try_insert(&mut self, chain_id: u64, token: Token) -> Result<(), Error> {
+        self.append(chain_id, &token)
+            .map_err(|err| Error::new(token.address.into(), err))
+    }
+
+    fn contains(&self, chain_id: u64, id: TokenId) -> bool {
+        matches!(self.lookup(chain_id, &id), Ok(Some(_)))
+    }
+
+    fn symbols(&'a self, chain_id: Option<u64>) -> Vec<String> {
+        self.records(KeyKind::Symbol)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(token_chain_id, _)| chain_id.is_none_or(|id| id == *token_chain_id))
+            .map(|(_, token)| token.symbol)
+            .collect()
+    }
+
+    /// Returns every token with the given symbol, reading the log once.
+    fn candidates(&'a self, chain_id: u64, symbol: &str) -> Vec<Self::Item> {
+        let Ok(preferred) = self.lookup(chain_id, &TokenId::Symbol(symbol.to_string())) else {
+            return Vec::new();
+        };
+        let preferred = preferred.map(|(offset, _)| offset);
+
+        let Ok(records) = self
+            .scan(|record_chain_id, token| record_chain_id == chain_id && token.symbol == symbol)
+        else {
+            return Vec::new();
+        };
+
+        let mut candidates = records
+            .into_iter()
+            .filter(|(offset, token)| matches!(self.is_current(chain_id, token, *offset), Ok(true)))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(offset, _)| Some(*offset) != preferred);
+
+        candidates
+            .into_iter()
+            .map(|(_, token)| Box::new(token))
+            .collect()
+    }
+
+    fn addresses(&'a self, chain_id: Option<u64>) -> Vec<Address> {
+        self.records(KeyKind::Address)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(token_chain_id, _)| chain_id.is_none_or(|id| id == *token_chain_id))
+            .map(|(_, token)| token.address)
+            .collect()
+    }
+}
+
+/// Returns the path of the index of the log at `path`.
+fn index_path(path: &Path) -> PathBuf {
+    let mut index_path = OsString::from(path);
+    index_path.push(".idx");
+
+    index_path.into()
+}
+
+fn insert_keys(index: &mut FileIndex, chain_id: u64, token: &Token, offset: u64) -> io::Result<()> {
+    index.insert(IndexKey::address(chain_id, token.address), offset)?;
+    index.insert(IndexKey::symbol(chain_id, &token.symbol), offset)
+}
+
+/// Flushes the directory entries of `path` to disk, for a rename to be
+/// durable.
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    File::open(parent)?.sync_all()
+}
+
+/// Directories can't be opened to be flushed on this platform.
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Encodes a record: its length as a little-endian `u32`, the chain id as a
+/// little-endian `u64`, and the token.
+fn encode_record(chain_id: u64, token: &Token) -> io::Result<Vec<u8>> {
+    let mut body = chain_id.to_le_bytes().to_vec();
+    codec::encode_token(token, &mut body)?;
+
+    let len = u32::try_from(body.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "token too large"))?;
+
+    let mut record = len.to_le_bytes().to_vec();
+    record.extend(body);
+
+    Ok(record)
+}
+
+/// Reads a record, returning its chain id, token and size on disk.
+///
+/// Fails with [`io::ErrorKind::UnexpectedEof`] only if the record runs
+/// past the end of `reader`, and [`io::ErrorKind::InvalidData`] if it is
+/// complete but invalid.
+fn read_record<R: Read>(reader: &mut R) -> io::Result<(u64, Token, u64)> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as u64;
+
+    let mut body = Vec::new();
+    reader.take(len).read_to_end(&mut body)?;
+
+    if (body.len() as u64) < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    if len < 8 {
+        return Err(codec::invalid_data("record too short"));
+    }
+
+    let (chain_id, mut bytes) = body.split_at(8);
+    let chain_id = u64::from_le_bytes(chain_id.try_into().expect("8 bytes"));
+    let token =
+        codec::decode_token(&mut bytes).map_err(|_| codec::invalid_data("invalid token record"))?;
+
+    if !bytes.is_empty() {
+        return Err(codec::invalid_data("trailing bytes in token record"));
+    }
+
+    Ok((chain_id, token, 4 + len))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{File, OpenOptions},
+        io::{self, Write},
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use alloy::primitives::Address;
+
+    use crate::{
+        mainnet::{DAI, USDC, WETH},
+        InternalError, Token, TokenId, TokenStore,
+    };
+
+    use super::{encode_record, index_path, FileTokenStore};
+
+    fn temp_path(name: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+
+        std::env::temp_dir().join(format!("alloy-erc20-{name}-{}-{nanos}", std::process::id()))
+    }
+
+    fn remove(path: PathBuf) {
+        std::fs::remove_file(index_path(&path)).unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_reopen() {
+        let path = temp_path("reopen");
+
+        {
+            let mut store = FileTokenStore::open(&path).unwrap();
+            store.insert(1, WETH.clone());
+            store.insert(1, USDC.clone().with_name("USD Coin".to_string()));
+            store.insert(10, DAI.clone());
+        }
+
+        let store = FileTokenStore::open(&path).unwrap();
+        let usdc = store.get(1, TokenId::Symbol("USDC".to_string())).unwrap();
+
+        assert_eq!(usdc.address, USDC.address);
+        assert_eq!(usdc.name.as_deref(), Some("USD Coin"));
+        assert!(store.contains(10, DAI.address.into()));
+        assert!(!store.contains(1, DAI.address.into()));
+        assert_eq!(store.addresses(Some(1)).len(), 2);
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_supersede_and_compact() {
+        let path = temp_path("compact");
+        let mut store = FileTokenStore::open(&path).unwrap();
+
+        store.insert(1, WETH.clone());
+        store.insert(1, Token::new(WETH.address, "WETH9".to_string(), 18));
+        store.insert(1, USDC.clone());
+        let len = std::fs::metadata(&path).unwrap().len();
+
+        assert!(!store.contains(1, TokenId::Symbol("WETH".to_string())));
+        assert_eq!(store.get(1, WETH.address.into()).unwrap().symbol, "WETH9");
+
+        store.compact().unwrap();
+        assert!(std::fs::metadata(&path).unwrap().len() < len);
+        assert_eq!(
+            store
+                .get(1, TokenId::Symbol("WETH9".to_string()))
+                .unwrap()
+                .address,
+            WETH.address
+        );
+
+        store.insert(1, DAI.clone());
+        drop(store);
+
+        let store = FileTokenStore::open(&path).unwrap();
+        assert_eq!(store.addresses(Some(1)).len(), 3);
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_supersede_and_reopen() {
+        let path = temp_path("supersede");
+
+        {
+            let mut store = FileTokenStore::open(&path).unwrap();
+            store.insert(1, WETH.clone());
+            store.insert(1, Token::new(WETH.address, "WETH9".to_string(), 18));
+        }
+
+        let store = FileTokenStore::open(&path).unwrap();
+
+        assert!(!store.contains(1, TokenId::Symbol("WETH".to_string())));
+        assert_eq!(store.symbols(Some(1)), ["WETH9"]);
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_corrupted_record() {
+        let path = temp_path("corrupted");
+
+        {
+            let mut store = FileTokenStore::open(&path).unwrap();
+            store.insert(1, WETH.clone());
+        }
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[2, 0, 0, 0, 1, 1]).unwrap();
+        file.write_all(&encode_record(1, &USDC).unwrap()).unwrap();
+        let len = file.metadata().unwrap().len();
+        drop(file);
+
+        let err = FileTokenStore::open(&path).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), len);
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_failed_write() {
+        let path = temp_path("failed");
+        let mut store = FileTokenStore::open(&path).unwrap();
+        store.insert(1, WETH.clone());
+
+        // A read-only handle fails every write
+        *store.lock() = File::open(&path).unwrap();
+        let err = store.try_insert(1, USDC.clone()).unwrap_err();
+
+        assert!(matches!(err.source, InternalError::Io(_)));
+        assert!(!store.contains(1, USDC.address.into()));
+        assert_eq!(store.get(1, WETH.address.into()).unwrap().symbol, "WETH");
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_candidates() {
+        let path = temp_path("candidates");
+        let mut store = FileTokenStore::open(&path).unwrap();
+        let clone = Token::new(DAI.address, "USDC".to_string(), 18);
+
+        store.insert(1, USDC.clone());
+        store.insert(1, WETH.clone());
+        store.insert(1, clone.clone());
+        store.insert(10, USDC.clone());
+
+        let candidates = store.candidates(1, "USDC");
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].address, clone.address);
+        assert_eq!(candidates[1].address, USDC.address);
+
+        store.insert(1, Token::new(USDC.address, "USDC.e".to_string(), 6));
+        assert_eq!(store.candidates(1, "USDC").len(), 1);
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_truncated_record() {
+        let path = temp_path("truncated");
+
+        {
+            let mut store = FileTokenStore::open(&path).unwrap();
+            store.insert(1, WETH.clone());
+        }
+
+        let len = std::fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[42, 0, 0, 0, 1])
+            .unwrap();
+
+        let mut store = FileTokenStore::open(&path).unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), len);
+
+        store.insert(1, USDC.clone());
+        drop(store);
+
+        let store = FileTokenStore::open(&path).unwrap();
+        assert!(store.contains(1, USDC.address.into()));
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_rebuild_index() {
+        let path = temp_path("rebuild");
+
+        {
+            let mut store = FileTokenStore::open(&path).unwrap();
+            store.insert(1, WETH.clone());
+            store.insert(1, Token::new(WETH.address, "WETH9".to_string(), 18));
+        }
+
+        std::fs::remove_file(index_path(&path)).unwrap();
+        let store = FileTokenStore::open(&path).unwrap();
+
+        assert_eq!(store.get(1, WETH.address.into()).unwrap().symbol, "WETH9");
+        assert!(!store.contains(1, TokenId::Symbol("WETH".to_string())));
+
+        drop(store);
+        std::fs::write(index_path(&path), b"garbage").unwrap();
+        let store = FileTokenStore::open(&path).unwrap();
+
+        assert_eq!(store.symbols(Some(1)), ["WETH9"]);
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_index_catches_up() {
+        let path = temp_path("catch-up");
+
+        {
+            let mut store = FileTokenStore::open(&path).unwrap();
+            store.insert(1, WETH.clone());
+        }
+
+        // Appended without updating the index, as after a crash
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&encode_record(1, &USDC).unwrap())
+            .unwrap();
+
+        let store = FileTokenStore::open(&path).unwrap();
+        assert!(store.contains(1, USDC.address.into()));
+        assert!(store.contains(1, TokenId::Symbol("WETH".to_string())));
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_many_tokens() {
+        let path = temp_path("many");
+
+        {
+            let mut store = FileTokenStore::open(&path).unwrap();
+
+            for i in 0..200 {
+                store.insert(
+                    1,
+                    Token::new(Address::with_last_byte(i), format!("T{i}"), 18),
+                );
+            }
+        }
+
+        let store = FileTokenStore::open(&path).unwrap();
+        assert_eq!(store.addresses(Some(1)).len(), 200);
+        assert_eq!(
+            store
+                .get(1, TokenId::Symbol("T42".to_string()))
+                .unwrap()
+                .address,
+            Address::with_last_byte(42)
+        );
+
+        remove(path);
+    }
+
+    #[test]
+    fn test_failed_compact() {
+        let path = temp_path("failed-compact");
+        let mut store = FileTokenStore::open(&path).unwrap();
+        store.insert(1, WETH.clone());
+
+        // A write-only handle fails every read
+        *store.lock() = OpenOptions::new().append(true).open(&path).unwrap();
+        assert!(store.compact().is_err());
+
+        let tmp_path = path.with_extension("compact");
+        assert!(!tmp_path.exists());
+        assert!(!index_path(&tmp_path).exists());
+
+        drop(store);
+        let store = FileTokenStore::open(&path).unwrap();
+        assert!(store.contains(1, WETH.address.into()));
+
+        remove(path);
+    }
+}
@@ -0,0 +1,418 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use alloy::primitives::{keccak256, Address};
+
+use crate::TokenId;
+
+/// The magic bytes starting an index file.
+const MAGIC: &[u8; 4] = b"E20I";
+
+/// The current index version.
+const VERSION: u8 = 1;
+
+/// The header size: magic, version, padding, then the capacity, the number
+/// of keys and the indexed log length as little-endian `u64`s.
+const HEADER_LEN: u64 = 32;
+
+/// The slot size: the key hash, the record offset as a little-endian
+/// `u64`, the key kind, and padding.
+const SLOT_LEN: u64 = 32;
+
+/// The capacity of a new index, in slots.
+const MIN_CAPACITY: u64 = 64;
+
+/// The number of slots read at once when scanning the index.
+const SCAN_BATCH: usize = 256;
+
+/// The kind of an index key, `0` marking an empty slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyKind {
+    Address = 1,
+    Symbol = 2,
+}
+
+/// A key of the index, identified by a 128-bit hash of its chain id and
+/// token id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IndexKey {
+    kind: KeyKind,
+    hash: [u8; 16],
+}
+
+impl IndexKey {
+    pub(crate) fn new(chain_id: u64, id: &TokenId) -> Self {
+        match id {
+            TokenId::Address(address) => Self::address(chain_id, *address),
+            TokenId::Symbol(symbol) => Self::symbol(chain_id, symbol),
+        }
+    }
+
+    pub(crate) fn address(chain_id: u64, address: Address) -> Self {
+        Self::hashed(KeyKind::Address, chain_id, address.as_slice())
+    }
+
+    pub(crate) fn symbol(chain_id: u64, symbol: &str) -> Self {
+        Self::hashed(KeyKind::Symbol, chain_id, symbol.as_bytes())
+    }
+
+    fn hashed(kind: KeyKind, chain_id: u64, id: &[u8]) -> Self {
+        let mut preimage = vec![kind as u8];
+        preimage.extend(chain_id.to_le_bytes());
+        preimage.extend(id);
+
+        let hash = keccak256(preimage);
+
+        Self {
+            kind,
+            hash: hash[..16].try_into().expect("16 bytes"),
+        }
+    }
+
+    /// Returns the slot the key is probed from.
+    fn home(&self, capacity: u64) -> u64 {
+        u64::from_le_bytes(self.hash[..8].try_into().expect("8 bytes")) & (capacity - 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    key: IndexKey,
+    offset: u64,
+}
+
+impl Slot {
+    fn encode(&self) -> [u8; SLOT_LEN as usize] {
+        let mut bytes = [0; SLOT_LEN as usize];
+        bytes[..16].copy_from_slice(&self.key.hash);
+        bytes[16..24].copy_from_slice(&self.offset.to_le_bytes());
+        bytes[24] = self.key.kind as u8;
+
+        bytes
+    }
+
+    /// Decodes a slot, returning `None` if it is empty.
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let kind = match bytes[24] {
+            1 => KeyKind::Address,
+            2 => KeyKind::Symbol,
+            _ => return None,
+        };
+
+        Some(Self {
+            key: IndexKey {
+                kind,
+                hash: bytes[..16].try_into().expect("16 bytes"),
+            },
+            offset: u64::from_le_bytes(bytes[16..24].try_into().expect("8 bytes")),
+        })
+    }
+}
+
+/// An on-disk hash table mapping the keys of a [`FileTokenStore`] to the
+/// offsets of their record in its log, with linear probing.
+///
+/// Only the header fields are kept in memory. The index doesn't compare
+/// the keys themselves, so callers check the record an offset points to.
+///
+/// [`FileTokenStore`]: crate::FileTokenStore
+#[derive(Debug)]
+pub(crate) struct FileIndex {
+    path: PathBuf,
+    file: File,
+    capacity: u64,
+    len: u64,
+    log_len: u64,
+}
+
+impl FileIndex {
+    /// Opens the index at `path`, returning `None` if it is missing or
+    /// invalid, for it to be rebuilt.
+    pub(crate) fn open(path: &Path) -> io::Result<Option<Self>> {
+        let mut file = match OpenOptions::new().read(true).write(true).open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let mut header = [0; HEADER_LEN as usize];
+        if file.read_exact(&mut header).is_err() || &header[..4] != MAGIC || header[4] != VERSION {
+            return Ok(None);
+        }
+
+        let field = |i: usize| u64::from_le_bytes(header[i..i + 8].try_into().expect("8 bytes"));
+        let (capacity, len, log_len) = (field(8), field(16), field(24));
+
+        let size = capacity
+            .checked_mul(SLOT_LEN)
+            .and_then(|size| size.checked_add(HEADER_LEN));
+
+        if !capacity.is_power_of_two()
+            || capacity < MIN_CAPACITY
+            || len >= capacity
+            || size != Some(file.metadata()?.len())
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            path: path.to_path_buf(),
+            file,
+            capacity,
+            len,
+            log_len,
+        }))
+    }
+
+    /// Creates an empty index at `path`, replacing any existing file.
+    pub(crate) fn create(path: &Path) -> io::Result<Self> {
+        Self::with_capacity(path, MIN_CAPACITY)
+    }
+
+    fn with_capacity(path: &Path, capacity: u64) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        // Zeroed slots are empty
+        file.set_len(HEADER_LEN + capacity * SLOT_LEN)?;
+
+        let mut index = Self {
+            path: path.to_path_buf(),
+            file,
+            capacity,
+            len: 0,
+            log_len: 0,
+        };
+        index.write_header()?;
+
+        Ok(index)
+    }
+
+    /// Returns the length of the log covered by the index.
+    pub(crate) const fn log_len(&self) -> u64 {
+        self.log_len
+    }
+
+    /// Records that the index covers the log up to `log_len`.
+    pub(crate) fn set_log_len(&mut self, log_len: u64) -> io::Result<()> {
+        self.log_len = log_len;
+        self.write_header()
+    }
+
+    /// Returns the offset of the record indexed with `key`.
+    pub(crate) fn get(&mut self, key: &IndexKey) -> io::Result<Option<u64>> {
+        Ok(self.find(key)?.1.map(|slot| slot.offset))
+    }
+
+    /// Indexes `key` at `offset`, replacing its previous offset.
+    pub(crate) fn insert(&mut self, key: IndexKey, offset: u64) -> io::Result<()> {
+        if (self.len + 1) * 2 > self.capacity {
+            self.grow()?;
+        }
+
+        let (position, existing) = self.find(&key)?;
+        self.write_slot(position, &Slot { key, offset })?;
+
+        if existing.is_none() {
+            self.len += 1;
+            self.write_header()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the offsets indexed with a key of the given `kind`.
+    pub(crate) fn offsets(&mut self, kind: KeyKind) -> io::Result<Vec<u64>> {
+        let mut offsets = Vec::new();
+
+        self.for_each_slot(|slot| {
+            if slot.key.kind == kind {
+                offsets.push(slot.offset);
+            }
+            Ok(())
+        })?;
+
+        Ok(offsets)
+    }
+
+    /// Moves the index file to `path`.
+    pub(crate) fn rename(&mut self, path: &Path) -> io::Result<()> {
+        std::fs::rename(&self.path, path)?;
+        self.path = path.to_path_buf();
+
+        Ok(())
+    }
+
+    /// Flushes the index file to disk.
+    pub(crate) fn sync(&self) -> io::Result<()> {
+        self.file.sync_all()
+    }
+
+    /// Returns the slot position of `key`, and its slot if it is indexed.
+    fn find(&mut self, key: &IndexKey) -> io::Result<(u64, Option<Slot>)> {
+        let mut position = key.home(self.capacity);
+
+        // The table is never more than half full, so an empty slot is found
+        loop {
+            match self.read_slot(position)? {
+                None => return Ok((position, None)),
+                Some(slot) if slot.key == *key => return Ok((position, Some(slot))),
+                Some(_) => position = (position + 1) & (self.capacity - 1),
+            }
+        }
+    }
+
+    /// Doubles the capacity, rehashing the slots into a new file.
+    fn grow(&mut self) -> io::Result<()> {
+        let tmp_path = self.path.with_extension("grow");
+        let mut grown = Self::with_capacity(&tmp_path, self.capacity * 2)?;
+
+        let result = self
+            .for_each_slot(|slot| {
+                let (position, _) = grown.find(&slot.key)?;
+                grown.write_slot(position, &slot)
+            })
+            .and_then(|()| {
+                grown.len = self.len;
+                grown.set_log_len(self.log_len)
+            })
+            .and_then(|()| grown.rename(&self.path));
+
+        if let Err(err) = result {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(err);
+        }
+
+        *self = grown;
+
+        Ok(())
+    }
+
+    fn for_each_slot<F>(&mut self, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(Slot) -> io::Result<()>,
+    {
+        self.file.seek(SeekFrom::Start(HEADER_LEN))?;
+
+        let mut reader = BufReader::new(&mut self.file);
+        let mut batch = vec![0; SCAN_BATCH * SLOT_LEN as usize];
+        let mut remaining = self.capacity as usize;
+
+        while remaining > 0 {
+            let count = remaining.min(SCAN_BATCH);
+            let bytes = &mut batch[..count * SLOT_LEN as usize];
+            reader.read_exact(bytes)?;
+
+            for slot in bytes
+                .chunks_exact(SLOT_LEN as usize)
+                .filter_map(Slot::decode)
+            {
+                f(slot)?;
+            }
+            remaining -= count;
+        }
+
+        Ok(())
+    }
+
+    fn read_slot(&mut self, position: u64) -> io::Result<Option<Slot>> {
+        let mut bytes = [0; SLOT_LEN as usize];
+        self.file
+            .seek(SeekFrom::Start(HEADER_LEN + position * SLOT_LEN))?;
+        self.file.read_exact(&mut bytes)?;
+
+        Ok(Slot::decode(&bytes))
+    }
+
+    fn write_slot(&mut self, position: u64, slot: &Slot) -> io::Result<()> {
+        self.file
+            .seek(SeekFrom::Start(HEADER_LEN + position * SLOT_LEN))?;
+        self.file.write_all(&slot.encode())
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        let mut header = [0; HEADER_LEN as usize];
+        header[..4].copy_from_slice(MAGIC);
+        header[4] = VERSION;
+        header[8..16].copy_from_slice(&self.capacity.to_le_bytes());
+        header[16..24].copy_from_slice(&self.len.to_le_bytes());
+        header[24..32].copy_from_slice(&self.log_len.to_le_bytes());
+
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(&header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use alloy::primitives::Address;
+
+    use super::{FileIndex, IndexKey, KeyKind, MIN_CAPACITY};
+
+    fn temp_path(name: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+
+        std::env::temp_dir().join(format!(
+            "alloy-erc20-index-{name}-{}-{nanos}",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_insert_grow_and_reopen() {
+        let path = temp_path("grow");
+        let mut index = FileIndex::create(&path).unwrap();
+        let keys = (0..MIN_CAPACITY)
+            .map(|i| IndexKey::address(1, Address::with_last_byte(i as u8)))
+            .collect::<Vec<_>>();
+
+        for (offset, key) in keys.iter().enumerate() {
+            index.insert(*key, offset as u64).unwrap();
+        }
+        index.insert(keys[0], 42).unwrap();
+        index.set_log_len(100).unwrap();
+        assert!(index.capacity > MIN_CAPACITY);
+        drop(index);
+
+        let mut index = FileIndex::open(&path).unwrap().unwrap();
+        assert_eq!(index.log_len(), 100);
+        assert_eq!(index.get(&keys[0]).unwrap(), Some(42));
+        assert_eq!(index.get(&keys[7]).unwrap(), Some(7));
+        assert_eq!(
+            index.get(&IndexKey::address(10, Address::ZERO)).unwrap(),
+            None
+        );
+        assert_eq!(index.get(&IndexKey::symbol(1, "USDC")).unwrap(), None);
+        assert_eq!(
+            index.offsets(KeyKind::Address).unwrap().len(),
+            MIN_CAPACITY as usize
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_index() {
+        let path = temp_path("invalid");
+
+        std::fs::write(&path, b"E20I").unwrap();
+        assert!(FileIndex::open(&path).unwrap().is_none());
+        assert!(FileIndex::open(&temp_path("missing")).unwrap().is_none());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
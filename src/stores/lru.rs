@@ -2,13 +2,17 @@ use std::num::NonZeroUsize;
 
 use alloy::primitives::Address;
 use lru::LruCache;
-use parking_lot::{MappedRwLockWriteGuard, RwLock, RwLockWriteGuard};
+use parking_lot::RwLock;
 
 use crate::{Token, TokenId};
 
-use super::TokenStore;
+use super::{StoreSnapshot, TokenStore};
 
-/// A basic [`TokenStore`] implementation.
+/// A [`TokenStore`] implementation keeping the most recently used tokens.
+///
+/// Tokens are returned as copies, so that no lock is held while using
+/// them. Each symbol resolves to the token last inserted with it, the previous
+/// one being only reachable by address, see [`TokenStore`].
 #[derive(Debug)]
 pub struct LruTokenStore {
     tokens: RwLock<LruCache<(u64, TokenId), Token>>,
@@ -24,13 +28,14 @@ impl LruTokenStore {
 }
 
 impl<'a> TokenStore<'a> for LruTokenStore {
-    type Item = MappedRwLockWriteGuard<'a, Token>;
+    type Item = Box<Token>;
 
     fn get(&'a self, chain_id: u64, id: TokenId) -> Option<Self::Item> {
-        RwLockWriteGuard::try_map(self.tokens.write(), |tokens| {
-            tokens.get_mut(&(chain_id, id))
-        })
-        .ok()
+        self.tokens
+            .write()
+            .get(&(chain_id, id))
+            .cloned()
+            .map(Box::new)
     }
 
     fn get_mut(&mut self, chain_id: u64, id: TokenId) -> Option<&mut Token> {
@@ -77,4 +82,81 @@ impl<'a> TokenStore<'a> for LruTokenStore {
             })
             .collect::<Vec<_>>()
     }
+
+    fn candidates(&'a self, chain_id: u64, symbol: &str) -> Vec<Self::Item> {
+        let mut tokens = self.tokens.write();
+
+        let preferred = tokens
+            .get(&(chain_id, TokenId::Symbol(symbol.to_string())))
+            .cloned();
+        let preferred_address = preferred.as_ref().map(|token| token.address);
+
+        // Iterating doesn't update the entries recency
+        let others = tokens
+            .iter()
+            .filter(|((token_chain_id, id), token)| {
+                *token_chain_id == chain_id
+                    && matches!(id, TokenId::Address(_))
+                    && token.symbol == symbol
+                    && Some(token.address) != preferred_address
+            })
+            .map(|(_, token)| token.clone())
+            .collect::<Vec<_>>();
+
+        preferred.into_iter().chain(others).map(Box::new).collect()
+    }
+}
+
+impl From<&LruTokenStore> for StoreSnapshot {
+    fn from(store: &LruTokenStore) -> Self {
+        let mut snapshot = StoreSnapshot::new();
+
+        // Iterating doesn't update the entries recency
+        for ((chain_id, id), token) in store.tokens.read().iter() {
+            if let TokenId::Address(_) = id {
+                snapshot.insert(*chain_id, token.clone());
+            }
+        }
+
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use alloy::primitives::address;
+
+    use super::LruTokenStore;
+    use crate::{mainnet::USDC, Token, TokenId, TokenStore};
+
+    #[test]
+    fn test_candidates() {
+        let mut store = LruTokenStore::new(NonZeroUsize::new(8).unwrap());
+        store.insert(1, USDC.clone());
+
+        let candidates = store.candidates(1, "USDC");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].address, USDC.address);
+
+        let clone = Token::new(
+            address!("1111111111111111111111111111111111111111"),
+            "USDC".to_string(),
+            18,
+        );
+        store.insert(1, clone.clone());
+
+        let candidates = store.candidates(1, "USDC");
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].address, clone.address);
+        assert_eq!(
+            store
+                .get(1, TokenId::Symbol("USDC".to_string()))
+                .unwrap()
+                .address,
+            clone.address
+        );
+        assert!(store.candidates(10, "USDC").is_empty());
+    }
 }
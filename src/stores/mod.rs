@@ -1,14 +1,31 @@
 mod basic;
-pub use basic::BasicTokenStore;
+mod codec;
+
+mod concurrent;
+pub use basic::{BasicTokenStore, ConflictPolicy};
+pub use concurrent::ConcurrentTokenStore;
 
 mod entry;
 pub use entry::Entry;
 
+#[cfg(feature = "file-store")]
+mod file;
+#[cfg(feature = "file-store")]
+pub use file::FileTokenStore;
+#[cfg(feature = "file-store")]
+mod file_index;
+
 #[cfg(feature = "lru-store")]
 mod lru;
 #[cfg(feature = "lru-store")]
 pub use lru::LruTokenStore;
 
+mod snapshot;
+pub use snapshot::StoreSnapshot;
+
+mod shared_token_store;
+pub use shared_token_store::SharedTokenStore;
+
 mod store_iter;
 pub use store_iter::StoreIter;
 
@@ -0,0 +1,43 @@
+use std::{future::Future, sync::Arc};
+
+use alloy::primitives::Address;
+use async_trait::async_trait;
+
+use crate::{Error, Token, TokenId};
+
+/// A [`Token`] store that can be shared between tasks, returning owned
+/// tokens.
+///
+/// Unlike [`TokenStore`], every method takes `&self`, so a single store can
+/// be used concurrently without being wrapped in a lock.
+///
+/// [`TokenStore`]: crate::TokenStore
+#[async_trait]
+pub trait SharedTokenStore: Send + Sync {
+    /// Returns the token corresponding to the given id.
+    fn get(&self, chain_id: u64, id: &TokenId) -> Option<Arc<Token>>;
+
+    /// Inserts a token into the store, returning it.
+    fn insert(&self, chain_id: u64, token: Token) -> Arc<Token>;
+
+    /// Returns the token at `address`, or fetches it with `fetch` and
+    /// inserts it into the store.
+    ///
+    /// Implementations should make concurrent misses for the same token
+    /// await a single `fetch`. The default implementation doesn't.
+    async fn get_or_fetch<F>(
+        &self,
+        chain_id: u64,
+        address: Address,
+        fetch: F,
+    ) -> Result<Arc<Token>, Error>
+    where
+        F: Future<Output = Result<Token, Error>> + Send,
+    {
+        if let Some(token) = self.get(chain_id, &address.into()) {
+            return Ok(token);
+        }
+
+        Ok(self.insert(chain_id, fetch.await?))
+    }
+}
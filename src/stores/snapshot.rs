@@ -0,0 +1,239 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, Read, Write},
+};
+
+use alloy::primitives::Address;
+
+use crate::{Error, Token};
+
+use super::{codec, TokenStore};
+
+/// A copy of the tokens of a store, partitioned by chain.
+///
+/// Snapshots can be saved to a compact binary format, or to JSON with the
+/// `serde` feature, and restored into any [`TokenStore`] to ship warm
+/// caches.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "BTreeMap<u64, Vec<Token>>", into = "BTreeMap<u64, Vec<Token>>")
+)]
+pub struct StoreSnapshot {
+    chains: BTreeMap<u64, Vec<Token>>,
+    /// The position of each token in its chain tokens.
+    positions: HashMap<(u64, Address), usize>,
+}
+
+impl StoreSnapshot {
+    /// Creates a new, empty, [`StoreSnapshot`].
+    pub fn new() -> Self {
+        Self {
+            chains: BTreeMap::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Inserts a token, replacing any token with the same address on the
+    /// same chain.
+    pub fn insert(&mut self, chain_id: u64, token: Token) {
+        let tokens = self.chains.entry(chain_id).or_default();
+
+        match self.positions.get(&(chain_id, token.address)) {
+            Some(position) => tokens[*position] = token,
+            None => {
+                self.positions
+                    .insert((chain_id, token.address), tokens.len());
+                tokens.push(token);
+            }
+        }
+    }
+
+    /// Returns the chains with tokens in the snapshot.
+    pub fn chain_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.chains.keys().copied()
+    }
+
+    /// Returns the tokens of the given chain.
+    pub fn tokens(&self, chain_id: u64) -> &[Token] {
+        self.chains.get(&chain_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the number of tokens, on all chains.
+    pub fn len(&self) -> usize {
+        self.chains.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if the snapshot has no tokens.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a snapshot with only the tokens of the given chains.
+    pub fn partition(&self, chain_ids: &[u64]) -> Self {
+        self.chains
+            .iter()
+            .filter(|(chain_id, _)| chain_ids.contains(chain_id))
+            .map(|(chain_id, tokens)| (*chain_id, tokens.clone()))
+            .collect::<BTreeMap<_, _>>()
+            .into()
+    }
+
+    /// Inserts every token of the snapshot into `store`.
+    ///
+    /// Tokens the store rejects, like a [`BasicTokenStore`] rejecting symbol
+    /// conflicts, are skipped and returned with their chain and the reason.
+    ///
+    /// [`BasicTokenStore`]: crate::BasicTokenStore
+    pub fn restore_into<'a, S: TokenStore<'a>>(&self, store: &mut S) -> Vec<(u64, &Token, Error)> {
+        self.chains
+            .iter()
+            .flat_map(|(chain_id, tokens)| tokens.iter().map(move |token| (*chain_id, token)))
+            .filter_map(|(chain_id, token)| {
+                store
+                    .try_insert(chain_id, token.clone())
+                    .err()
+                    .map(|err| (chain_id, token, err))
+            })
+            .collect()
+    }
+
+    /// Writes the snapshot in a compact binary format.
+    pub fn write_binary<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        codec::encode_chains(&self.chains, &mut writer)
+    }
+
+    /// Reads a snapshot written with [`StoreSnapshot::write_binary`].
+    pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Self> {
+        Ok(codec::decode_chains(&mut reader)?.into())
+    }
+
+    /// Writes the snapshot as JSON, keyed by chain id.
+    #[cfg(feature = "serde")]
+    pub fn write_json<W: Write>(&self, writer: W) -> io::Result<()> {
+        Ok(serde_json::to_writer(writer, self)?)
+    }
+
+    /// Reads a snapshot written with [`StoreSnapshot::write_json`].
+    #[cfg(feature = "serde")]
+    pub fn read_json<R: Read>(reader: R) -> io::Result<Self> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+impl From<BTreeMap<u64, Vec<Token>>> for StoreSnapshot {
+    fn from(chains: BTreeMap<u64, Vec<Token>>) -> Self {
+        let mut snapshot = Self::new();
+
+        for (chain_id, tokens) in chains {
+            snapshot.chains.entry(chain_id).or_default();
+
+            for token in tokens {
+                snapshot.insert(chain_id, token);
+            }
+        }
+
+        snapshot
+    }
+}
+
+impl From<StoreSnapshot> for BTreeMap<u64, Vec<Token>> {
+    fn from(snapshot: StoreSnapshot) -> Self {
+        snapshot.chains
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Address;
+
+    use crate::{
+        arbitrum,
+        mainnet::{USDC, WETH},
+        stores::{BasicTokenStore, ConflictPolicy},
+        Token, TokenId, TokenStore,
+    };
+
+    use super::StoreSnapshot;
+
+    fn store() -> BasicTokenStore {
+        let mut store = BasicTokenStore::new();
+
+        store.insert(1, WETH.clone());
+        store.insert(1, USDC.clone());
+        store.insert(42161, arbitrum::USDC.clone());
+
+        store
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let store = store();
+
+        let snapshot = store.snapshot(&[1]);
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.tokens(42161).is_empty());
+
+        let snapshot = StoreSnapshot::from(&store);
+        assert_eq!(snapshot.chain_ids().collect::<Vec<_>>(), vec![1, 42161]);
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.partition(&[42161]).len(), 1);
+    }
+
+    #[test]
+    fn test_insert_replaces() {
+        let mut snapshot = StoreSnapshot::new();
+        snapshot.insert(1, USDC.clone());
+        snapshot.insert(1, WETH.clone());
+        snapshot.insert(10, USDC.clone());
+        snapshot.insert(1, USDC.clone().with_name("USD Coin".to_string()));
+
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.tokens(1)[0].name.as_deref(), Some("USD Coin"));
+        assert_eq!(snapshot.tokens(10)[0].name, None);
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let mut buf = Vec::new();
+        StoreSnapshot::from(&store())
+            .write_binary(&mut buf)
+            .unwrap();
+
+        let mut restored = BasicTokenStore::new();
+        let snapshot = StoreSnapshot::read_binary(buf.as_slice()).unwrap();
+        assert!(snapshot.restore_into(&mut restored).is_empty());
+        assert!(restored.contains(1, TokenId::Symbol("WETH".to_string())));
+        assert!(restored.contains(1, USDC.address.into()));
+        assert!(restored.contains(42161, arbitrum::USDC.address.into()));
+        assert!(!restored.contains(42161, WETH.address.into()));
+    }
+
+    #[test]
+    fn test_restore_rejected() {
+        let mut snapshot = StoreSnapshot::from(&store());
+        let clone = Token::new(Address::repeat_byte(1), "USDC".to_string(), 6);
+        snapshot.insert(1, clone.clone());
+
+        let mut restored = BasicTokenStore::with_conflict_policy(ConflictPolicy::Reject);
+        let rejected = snapshot.restore_into(&mut restored);
+
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].0, 1);
+        assert_eq!(rejected[0].1.address, clone.address);
+        assert_eq!(restored.addresses(Some(1)).len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_roundtrip() {
+        let mut buf = Vec::new();
+        StoreSnapshot::from(&store()).write_json(&mut buf).unwrap();
+
+        let snapshot = StoreSnapshot::read_json(buf.as_slice()).unwrap();
+
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.tokens(42161)[0].address, arbitrum::USDC.address);
+    }
+}
@@ -2,11 +2,19 @@ use std::ops::Deref;
 
 use alloy::primitives::Address;
 
-use crate::{Token, TokenId};
+use crate::{Error, Token, TokenId};
 
-use super::{Entry, StoreIter};
+use super::{Entry, StoreIter, StoreSnapshot};
 
 /// A [`Token`] store
+///
+/// Tokens are keyed by chain id and address. Only [`BasicTokenStore`] keeps
+/// many tokens sharing a symbol on a chain, according to its
+/// [`ConflictPolicy`]: other stores resolve a symbol to the token last
+/// inserted with it.
+///
+/// [`BasicTokenStore`]: crate::BasicTokenStore
+/// [`ConflictPolicy`]: crate::ConflictPolicy
 pub trait TokenStore<'a>: Sized {
     /// a reference to a token.
     type Item: Deref<Target = Token>;
@@ -20,6 +28,17 @@ pub trait TokenStore<'a>: Sized {
     /// Inserts a token into the store.
     fn insert(&mut self, chain_id: u64, token: Token);
 
+    /// Inserts a token into the store, failing if the store can't hold it,
+    /// like a [`BasicTokenStore`] rejecting symbol conflicts.
+    ///
+    /// The default implementation never fails.
+    ///
+    /// [`BasicTokenStore`]: crate::BasicTokenStore
+    fn try_insert(&mut self, chain_id: u64, token: Token) -> Result<(), Error> {
+        self.insert(chain_id, token);
+        Ok(())
+    }
+
     /// Returns `true` if the store contains a value for the specified `id`.
     fn contains(&self, chain_id: u64, id: TokenId) -> bool;
 
@@ -29,6 +48,26 @@ pub trait TokenStore<'a>: Sized {
     /// Returns the addresses from all the tokens in the store.
     fn addresses(&'a self, chain_id: Option<u64>) -> Vec<Address>;
 
+    /// Returns every token with the given symbol, the one the symbol is
+    /// looked up to first.
+    ///
+    /// The default implementation looks every token of the chain up: stores
+    /// whose items hold a lock, or reading tokens from disk, must override
+    /// it.
+    fn candidates(&'a self, chain_id: u64, symbol: &str) -> Vec<Self::Item> {
+        let preferred = self
+            .get(chain_id, TokenId::Symbol(symbol.to_string()))
+            .map(|token| token.address);
+
+        let mut candidates = self
+            .iter(chain_id)
+            .filter(|token| token.symbol == symbol)
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|token| Some(token.address) != preferred);
+
+        candidates
+    }
+
     /// Gets the entry for the given token id.
     fn entry(&'a mut self, chain_id: u64, id: TokenId) -> Entry<'a, Self> {
         Entry::new(chain_id, id, self)
@@ -39,22 +78,25 @@ pub trait TokenStore<'a>: Sized {
         StoreIter::new(self, chain_id)
     }
 
+    /// Returns a snapshot of the tokens of the given chains.
+    fn snapshot(&'a self, chain_ids: &[u64]) -> StoreSnapshot {
+        let mut snapshot = StoreSnapshot::new();
+
+        for chain_id in chain_ids {
+            for token in self.iter(*chain_id) {
+                snapshot.insert(*chain_id, token.clone());
+            }
+        }
+
+        snapshot
+    }
+
     #[cfg(feature = "known-tokens")]
-    /// Insert a few well known token to the store.
+    /// Inserts the known tokens of the given chain into the store, see
+    /// [`known_tokens`](crate::known_tokens).
     fn insert_known_tokens(&mut self, chain_id: u64) {
-        use crate::{arbitrum, mainnet};
-
-        if chain_id == 1 {
-            self.insert(chain_id, mainnet::WETH.to_owned());
-            self.insert(chain_id, mainnet::WBTC.to_owned());
-            self.insert(chain_id, mainnet::USDC.to_owned());
-            self.insert(chain_id, mainnet::USDT.to_owned());
-            self.insert(chain_id, mainnet::DAI.to_owned());
-        } else if chain_id == 42161 {
-            // Arbitrum
-            self.insert(chain_id, arbitrum::WETH.to_owned());
-            self.insert(chain_id, arbitrum::USDC.to_owned());
-            self.insert(chain_id, arbitrum::USDT.to_owned());
+        for token in crate::known_tokens(chain_id) {
+            self.insert(chain_id, token.clone());
         }
     }
 }
@@ -1,11 +1,17 @@
+use std::str::FromStr;
+
 use alloy::primitives::{Address, U256};
-use bigdecimal::{
-    num_bigint::{BigInt, Sign},
-    BigDecimal,
-};
+use bigdecimal::BigDecimal;
+
+use crate::{error::InternalError, units, Error, FormatOptions, Rounding};
 
 /// A token.
-#[derive(Debug, Clone)]
+///
+/// Tokens compare by value, all their fields being equal. A token carries
+/// no chain id, so it doesn't identify a token across chains: stores and
+/// token lists key tokens by chain id and address instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Token {
     /// The token address.
     pub address: Address,
@@ -13,6 +19,12 @@ pub struct Token {
     pub symbol: String,
     /// The token decimals
     pub decimals: u8,
+    /// The token name, if known.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub name: Option<String>,
 }
 
 impl Token {
@@ -22,20 +34,50 @@ impl Token {
             address,
             symbol,
             decimals,
+            name: None,
         }
     }
 
+    /// Sets the token name.
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
     /// Gets the token balance as a [`BigDecimal`]
     pub fn get_balance(&self, amount: U256) -> BigDecimal {
-        BigDecimal::from((
-            BigInt::from_bytes_be(Sign::Plus, &amount.to_be_bytes::<{ U256::BYTES }>()),
-            self.decimals as i64,
-        ))
+        units::to_decimal(amount, self.decimals)
+    }
+
+    /// Converts a decimal `value`, like `1.5`, to base units of the token.
+    ///
+    /// This is the inverse of [`Token::get_balance`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is negative, doesn't fit in a `U256`, or
+    /// has more decimals than the token with [`Rounding::Exact`].
+    pub fn to_base_units(&self, value: &BigDecimal, rounding: Rounding) -> Result<U256, Error> {
+        units::parse_units(value, self.decimals, rounding)
+            .map_err(|err| Error::new(self.address.into(), err))
+    }
+
+    /// Parses a decimal string, like `"1.5"`, to base units of the token.
+    ///
+    /// See [`Token::to_base_units`].
+    pub fn parse_units(&self, value: &str, rounding: Rounding) -> Result<U256, Error> {
+        let decimal = BigDecimal::from_str(value.trim()).map_err(|_| {
+            Error::new(
+                self.address.into(),
+                InternalError::InvalidAmount(value.to_string()),
+            )
+        })?;
+
+        self.to_base_units(&decimal, rounding)
     }
-}
 
-impl PartialEq for Token {
-    fn eq(&self, other: &Self) -> bool {
-        self.symbol == other.symbol
+    /// Formats `amount` base units of the token with the given `options`.
+    pub fn format_units(&self, amount: U256, options: &FormatOptions) -> String {
+        units::format_units(amount, self.decimals, &self.symbol, options)
     }
 }
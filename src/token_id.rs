@@ -9,6 +9,7 @@ use alloy::primitives::Address;
 
 /// A token identifier.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TokenId {
     /// Identify a token by its symbol
     Symbol(String),
@@ -0,0 +1,807 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::{Display, Formatter},
+    io::{Read, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Token, TokenStore};
+
+/// The maximum number of tokens in a list, per the schema.
+const MAX_TOKENS: usize = 10_000;
+
+/// The maximum number of keywords of a list, per the schema.
+const MAX_KEYWORDS: usize = 20;
+
+/// The maximum number of tags defined by a list, per the schema.
+const MAX_TAGS: usize = 20;
+
+/// The maximum number of tags of a token, per the schema.
+const MAX_TOKEN_TAGS: usize = 10;
+
+/// A [Token List], as specified by the [tokenlists.org] schema.
+///
+/// [Token List]: https://github.com/Uniswap/token-lists
+/// [tokenlists.org]: https://tokenlists.org
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenList {
+    /// The list name.
+    pub name: String,
+    /// The list last update, as an RFC 3339 date-time.
+    pub timestamp: String,
+    /// The list version.
+    pub version: Version,
+    /// The listed tokens.
+    pub tokens: Vec<TokenInfo>,
+    /// The list logo URI.
+    #[serde(rename = "logoURI", default, skip_serializing_if = "Option::is_none")]
+    pub logo_uri: Option<String>,
+    /// Keywords describing the list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<Vec<String>>,
+    /// The definitions of the tags used by the tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<serde_json::Value>,
+}
+
+/// A token of a [`TokenList`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    /// The chain the token is deployed on.
+    pub chain_id: u64,
+    /// The token address.
+    pub address: Address,
+    /// The token name.
+    pub name: String,
+    /// The token decimals.
+    pub decimals: u8,
+    /// The token symbol.
+    pub symbol: String,
+    /// The token logo URI.
+    #[serde(rename = "logoURI", default, skip_serializing_if = "Option::is_none")]
+    pub logo_uri: Option<String>,
+    /// The token tags, defined in [`TokenList::tags`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Additional token information.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<serde_json::Value>,
+}
+
+/// A [`TokenList`] semantic version.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Version {
+    /// Incremented when tokens are removed.
+    pub major: u32,
+    /// Incremented when tokens are added.
+    pub minor: u32,
+    /// Incremented when tokens are changed.
+    pub patch: u32,
+}
+
+/// Token list related errors.
+#[derive(thiserror::Error, Debug)]
+pub enum TokenListError {
+    /// The list isn't valid JSON, or doesn't match the schema types.
+    #[error("Failed to read token list: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The list name is invalid.
+    #[error("Invalid token list name: {0}")]
+    InvalidName(String),
+    /// The list has no tokens, or too many.
+    #[error("Invalid token list size: {0}")]
+    InvalidSize(usize),
+    /// A list field other than its name or tokens is invalid.
+    #[error("Invalid token list: {0}")]
+    InvalidList(&'static str),
+    /// A token is invalid.
+    #[error("Invalid token {address} on chain {chain_id}: {reason}")]
+    InvalidToken {
+        /// The token chain.
+        chain_id: u64,
+        /// The token address.
+        address: Address,
+        /// Why the token is invalid.
+        reason: &'static str,
+    },
+    /// A token is listed twice.
+    #[error("Duplicate token {address} on chain {chain_id}")]
+    DuplicateToken {
+        /// The token chain.
+        chain_id: u64,
+        /// The token address.
+        address: Address,
+    },
+}
+
+impl TokenList {
+    /// Reads and validates a token list from JSON.
+    pub fn read_json<R: Read>(reader: R) -> Result<Self, TokenListError> {
+        let list: Self = serde_json::from_reader(reader)?;
+        list.validate()?;
+
+        Ok(list)
+    }
+
+    /// Writes the token list as JSON.
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<(), TokenListError> {
+        Ok(serde_json::to_writer_pretty(writer, self)?)
+    }
+
+    /// Validates the token list against the [tokenlists.org] schema
+    /// constraints, and checks that no token is listed twice.
+    ///
+    /// [tokenlists.org]: https://tokenlists.org
+    pub fn validate(&self) -> Result<(), TokenListError> {
+        if !matches(&self.name, 1, 30, |c| is_word(c) || c == ' ') {
+            return Err(TokenListError::InvalidName(self.name.clone()));
+        }
+
+        if self.tokens.is_empty() || self.tokens.len() > MAX_TOKENS {
+            return Err(TokenListError::InvalidSize(self.tokens.len()));
+        }
+
+        if !is_rfc3339(&self.timestamp) {
+            return Err(TokenListError::InvalidList(
+                "timestamp must be an RFC 3339 date-time",
+            ));
+        }
+
+        if self.logo_uri.as_deref().is_some_and(|uri| !is_uri(uri)) {
+            return Err(TokenListError::InvalidList("logo URI must be a valid URI"));
+        }
+
+        if let Some(keywords) = &self.keywords {
+            let unique = keywords.iter().collect::<HashSet<_>>();
+
+            if keywords.len() > MAX_KEYWORDS
+                || unique.len() != keywords.len()
+                || !keywords
+                    .iter()
+                    .all(|keyword| matches(keyword, 1, 20, |c| is_word(c) || c == ' '))
+            {
+                return Err(TokenListError::InvalidList(
+                    "keywords must be at most 20 unique words of 1 to 20 characters",
+                ));
+            }
+        }
+
+        if let Some(tags) = &self.tags {
+            validate_tags(tags)?;
+        }
+
+        let mut seen = HashSet::with_capacity(self.tokens.len());
+
+        for token in &self.tokens {
+            token.validate()?;
+
+            if !seen.insert((token.chain_id, token.address)) {
+                return Err(TokenListError::DuplicateToken {
+                    chain_id: token.chain_id,
+                    address: token.address,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Inserts every listed token into `store`.
+    ///
+    /// Tokens the store rejects, like a [`BasicTokenStore`] rejecting symbol
+    /// conflicts, are skipped and returned along with the reason.
+    ///
+    /// [`BasicTokenStore`]: crate::BasicTokenStore
+    pub fn insert_into<'a, S: TokenStore<'a>>(&self, store: &mut S) -> Vec<(&TokenInfo, Error)> {
+        self.tokens
+            .iter()
+            .filter_map(|token| {
+                store
+                    .try_insert(token.chain_id, Token::from(token))
+                    .err()
+                    .map(|err| (token, err))
+            })
+            .collect()
+    }
+
+    /// Exports the tokens of `store` on the given chains as a token list.
+    ///
+    /// If a `previous` version of the list is given, the version is bumped
+    /// from it: the major version when tokens are removed, the minor
+    /// version when tokens are added, and the patch version when tokens
+    /// are changed. Logos, tags and extensions of the tokens still listed
+    /// are kept, as they aren't part of [`Token`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the exported list isn't valid, see
+    /// [`TokenList::validate`], like when a token symbol doesn't fit the
+    /// schema.
+    pub fn from_store<'a, S: TokenStore<'a>>(
+        name: impl Into<String>,
+        store: &'a S,
+        chain_ids: &[u64],
+        previous: Option<&Self>,
+    ) -> Result<Self, TokenListError> {
+        let snapshot = store.snapshot(chain_ids);
+        let previous_tokens = &previous
+            .map(|list| {
+                list.tokens
+                    .iter()
+                    .map(|token| ((token.chain_id, token.address), token))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .unwrap_or_default();
+
+        let mut tokens = snapshot
+            .chain_ids()
+            .flat_map(|chain_id| {
+                snapshot.tokens(chain_id).iter().map(move |token| {
+                    let mut info = TokenInfo::new(chain_id, token);
+
+                    if let Some(previous) = previous_tokens.get(&(chain_id, token.address)) {
+                        info.logo_uri.clone_from(&previous.logo_uri);
+                        info.tags.clone_from(&previous.tags);
+                        info.extensions.clone_from(&previous.extensions);
+                    }
+
+                    info
+                })
+            })
+            .collect::<Vec<_>>();
+
+        tokens.sort_by_key(|token| (token.chain_id, token.address));
+
+        let version = match previous {
+            Some(previous) => previous.version.bump(&previous.tokens, &tokens),
+            None => Version::new(1, 0, 0),
+        };
+
+        let list = Self {
+            name: name.into(),
+            timestamp: now_rfc3339(),
+            version,
+            tokens,
+            logo_uri: previous.and_then(|list| list.logo_uri.clone()),
+            keywords: previous.and_then(|list| list.keywords.clone()),
+            tags: previous.and_then(|list| list.tags.clone()),
+        };
+        list.validate()?;
+
+        Ok(list)
+    }
+}
+
+impl TokenInfo {
+    /// Creates a new [`TokenInfo`] from a token on the given chain.
+    ///
+    /// Tokens without a known name are listed with their symbol as name.
+    pub fn new(chain_id: u64, token: &Token) -> Self {
+        Self {
+            chain_id,
+            address: token.address,
+            name: token.name.clone().unwrap_or_else(|| token.symbol.clone()),
+            decimals: token.decimals,
+            symbol: token.symbol.clone(),
+            logo_uri: None,
+            tags: None,
+            extensions: None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), TokenListError> {
+        let reason = if self.chain_id == 0 {
+            Some("chain id must be positive")
+        } else if !matches(&self.name, 1, 60, |_| true) {
+            Some("name must be 1 to 60 characters long")
+        } else if !matches(&self.symbol, 1, 20, |c| {
+            c.is_ascii_alphanumeric() || "+-%/$.".contains(c)
+        }) {
+            Some("symbol must be 1 to 20 letters, digits or `+-%/$.` characters")
+        } else if self.logo_uri.as_deref().is_some_and(|uri| !is_uri(uri)) {
+            Some("logo URI must be a valid URI")
+        } else if self
+            .tags
+            .as_ref()
+            .is_some_and(|tags| tags.len() > MAX_TOKEN_TAGS)
+        {
+            Some("at most 10 tags are allowed")
+        } else if self
+            .tags
+            .iter()
+            .flatten()
+            .any(|tag| !is_tag_identifier(tag))
+        {
+            Some("tags must be 1 to 10 word characters")
+        } else {
+            None
+        };
+
+        match reason {
+            Some(reason) => Err(TokenListError::InvalidToken {
+                chain_id: self.chain_id,
+                address: self.address,
+                reason,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `true` if the token fields from `other` differ.
+    fn differs(&self, other: &Self) -> bool {
+        self.name != other.name
+            || self.decimals != other.decimals
+            || self.symbol != other.symbol
+            || self.logo_uri != other.logo_uri
+            || self.tags != other.tags
+            || self.extensions != other.extensions
+    }
+}
+
+impl From<&TokenInfo> for Token {
+    fn from(info: &TokenInfo) -> Self {
+        Token::new(info.address, info.symbol.clone(), info.decimals).with_name(info.name.clone())
+    }
+}
+
+impl Version {
+    /// Creates a new [`Version`].
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Returns the version following this one, for a list going from the
+    /// `previous` tokens to the `next` ones.
+    pub fn bump(&self, previous: &[TokenInfo], next: &[TokenInfo]) -> Self {
+        let key = |token: &TokenInfo| (token.chain_id, token.address);
+        let previous = previous
+            .iter()
+            .map(|token| (key(token), token))
+            .collect::<BTreeMap<_, _>>();
+        let next = next
+            .iter()
+            .map(|token| (key(token), token))
+            .collect::<BTreeMap<_, _>>();
+
+        if previous.keys().any(|key| !next.contains_key(key)) {
+            Self::new(self.major + 1, 0, 0)
+        } else if next.keys().any(|key| !previous.contains_key(key)) {
+            Self::new(self.major, self.minor + 1, 0)
+        } else if next.iter().any(|(key, token)| token.differs(previous[key])) {
+            Self::new(self.major, self.minor, self.patch + 1)
+        } else {
+            *self
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Validates the tag definitions of a list: an object mapping at most 20 tag
+/// identifiers to their `name` and `description`.
+fn validate_tags(tags: &serde_json::Value) -> Result<(), TokenListError> {
+    let invalid = |reason| Err(TokenListError::InvalidList(reason));
+
+    let Some(tags) = tags.as_object() else {
+        return invalid("tags must be an object");
+    };
+
+    if tags.len() > MAX_TAGS {
+        return invalid("at most 20 tags are allowed");
+    }
+
+    for (id, definition) in tags {
+        if !is_tag_identifier(id) {
+            return invalid("tag identifiers must be 1 to 10 word characters");
+        }
+
+        let Some(definition) = definition.as_object() else {
+            return invalid("tag definitions must be objects");
+        };
+
+        if definition
+            .keys()
+            .any(|key| key != "name" && key != "description")
+        {
+            return invalid("tag definitions only have a name and a description");
+        }
+
+        let field = |key| definition.get(key).and_then(serde_json::Value::as_str);
+
+        if !field("name").is_some_and(|name| matches(name, 1, 21, |c| is_word(c) || c == ' ')) {
+            return invalid("tag names must be 1 to 21 word characters or spaces");
+        }
+
+        if !field("description").is_some_and(|description| {
+            matches(description, 1, 200, |c| is_word(c) || " .,:".contains(c))
+        }) {
+            return invalid("tag descriptions must be 1 to 200 word characters, spaces or `.,:`");
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if `value` is `min` to `max` characters long, all of them
+/// `allowed`.
+fn matches(value: &str, min: usize, max: usize, allowed: impl Fn(char) -> bool) -> bool {
+    (min..=max).contains(&value.chars().count()) && value.chars().all(allowed)
+}
+
+/// Returns `true` if `c` matches the `\w` class of the schema patterns, which
+/// is ASCII only.
+const fn is_word(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Returns `true` if `tag` is a valid tag identifier.
+fn is_tag_identifier(tag: &str) -> bool {
+    matches(tag, 1, 10, is_word)
+}
+
+/// Returns `true` if `uri` is an absolute [RFC 3986] URI, like the `uri`
+/// format of the schema.
+///
+/// [RFC 3986]: https://www.rfc-editor.org/rfc/rfc3986
+fn is_uri(uri: &str) -> bool {
+    let Some((scheme, rest)) = uri.split_once(':') else {
+        return false;
+    };
+
+    let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    let valid_rest = !rest.is_empty()
+        && rest
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-._~:/?#[]@!$&'()*+,;=%".contains(c));
+    let valid_escapes = rest.split('%').skip(1).all(|escape| {
+        escape.len() >= 2 && escape.as_bytes()[..2].iter().all(u8::is_ascii_hexdigit)
+    });
+
+    valid_scheme && valid_rest && valid_escapes
+}
+
+/// Returns `true` if `timestamp` is an [RFC 3339] date-time, like the
+/// `date-time` format of the schema.
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+fn is_rfc3339(timestamp: &str) -> bool {
+    let bytes = timestamp.as_bytes();
+    let digits = |range: std::ops::Range<usize>| {
+        bytes
+            .get(range.clone())
+            .filter(|digits| digits.iter().all(u8::is_ascii_digit))
+            .map(|_| timestamp[range].parse::<u32>().unwrap_or_default())
+    };
+    let separator = |index: usize, expected: &[u8]| {
+        bytes.get(index).is_some_and(|byte| expected.contains(byte))
+    };
+
+    let (Some(_), Some(month), Some(day), Some(hour), Some(minute), Some(second)) = (
+        digits(0..4),
+        digits(5..7),
+        digits(8..10),
+        digits(11..13),
+        digits(14..16),
+        digits(17..19),
+    ) else {
+        return false;
+    };
+
+    if !(separator(4, b"-")
+        && separator(7, b"-")
+        && separator(10, b"Tt")
+        && separator(13, b":")
+        && separator(16, b":")
+        && (1..=12).contains(&month)
+        && (1..=31).contains(&day)
+        && hour < 24
+        && minute < 60
+        && second <= 60)
+    {
+        return false;
+    }
+
+    let mut offset = 19;
+    if separator(offset, b".") {
+        let fraction = bytes[offset + 1..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        if fraction == 0 {
+            return false;
+        }
+        offset += fraction + 1;
+    }
+
+    match &bytes[offset..] {
+        [b'Z' | b'z'] => true,
+        [b'+' | b'-', ..] => {
+            let (Some(hours), Some(minutes)) = (
+                digits(offset + 1..offset + 3),
+                digits(offset + 4..offset + 6),
+            ) else {
+                return false;
+            };
+
+            bytes.len() == offset + 6 && separator(offset + 3, b":") && hours < 24 && minutes < 60
+        }
+        _ => false,
+    }
+}
+
+/// Returns the current UTC time as an RFC 3339 date-time.
+fn now_rfc3339() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    rfc3339(secs)
+}
+
+/// Formats a UNIX timestamp as an RFC 3339 UTC date-time.
+fn rfc3339(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let secs = secs % 86_400;
+
+    // Days to civil date, from Howard Hinnant's algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::Address;
+    use serde_json::json;
+
+    use super::{is_rfc3339, rfc3339, TokenInfo, TokenList, TokenListError, Version};
+    use crate::{mainnet, BasicTokenStore, ConflictPolicy, Token, TokenId, TokenStore};
+
+    const LIST: &str = r#"{
+        "name": "Test List",
+        "timestamp": "2024-01-01T00:00:00Z",
+        "version": { "major": 1, "minor": 2, "patch": 3 },
+        "tokens": [
+            {
+                "chainId": 1,
+                "address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "name": "USD Coin",
+                "decimals": 6,
+                "symbol": "USDC",
+                "logoURI": "https://example.com/usdc.png",
+                "tags": ["stablecoin"]
+            },
+            {
+                "chainId": 42161,
+                "address": "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+                "name": "USD Coin",
+                "decimals": 6,
+                "symbol": "USDC"
+            }
+        ],
+        "tags": { "stablecoin": { "name": "Stablecoin", "description": "Pegged" } }
+    }"#;
+
+    #[test]
+    fn test_read_and_insert() {
+        let list = TokenList::read_json(LIST.as_bytes()).unwrap();
+        assert_eq!(list.version, Version::new(1, 2, 3));
+
+        let mut store = BasicTokenStore::new();
+        list.insert_into(&mut store);
+
+        let usdc = store.get(1, TokenId::Symbol("USDC".to_string())).unwrap();
+        assert_eq!(usdc.address, mainnet::USDC.address);
+        assert_eq!(usdc.name.as_deref(), Some("USD Coin"));
+        assert!(store.contains(42161, TokenId::Symbol("USDC".to_string())));
+    }
+
+    /// Asserts that `change` makes the test list invalid.
+    fn assert_rejects(change: impl FnOnce(&mut TokenList)) -> TokenListError {
+        let mut list = TokenList::read_json(LIST.as_bytes()).unwrap();
+        change(&mut list);
+
+        list.validate().unwrap_err()
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(matches!(
+            assert_rejects(|list| list.tokens.push(list.tokens[0].clone())),
+            TokenListError::DuplicateToken { chain_id: 1, .. }
+        ));
+        assert!(matches!(
+            assert_rejects(|list| list.tokens.clear()),
+            TokenListError::InvalidSize(0)
+        ));
+    }
+
+    #[test]
+    fn test_validate_name() {
+        for name in ["", "Not-valid!", "Tökens", &"a".repeat(31)] {
+            assert!(matches!(
+                assert_rejects(|list| list.name = name.to_string()),
+                TokenListError::InvalidName(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_list_fields() {
+        let rejects = [
+            assert_rejects(|list| list.timestamp = "2024-01-01".to_string()),
+            assert_rejects(|list| list.timestamp = "2024-13-01T00:00:00Z".to_string()),
+            assert_rejects(|list| list.logo_uri = Some("example.com/logo.png".to_string())),
+            assert_rejects(|list| list.logo_uri = Some("https://exa mple.com".to_string())),
+            assert_rejects(|list| list.keywords = Some(vec!["a".to_string(); 2])),
+            assert_rejects(|list| list.keywords = Some(vec!["not-valid".to_string()])),
+            assert_rejects(|list| list.keywords = Some(vec!["a".repeat(21)])),
+            assert_rejects(|list| {
+                list.keywords = Some((0..21).map(|i| i.to_string()).collect());
+            }),
+        ];
+
+        for err in rejects {
+            assert!(matches!(err, TokenListError::InvalidList(_)), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_validate_tags() {
+        let tag =
+            |name: &str, description: &str| json!({ "name": name, "description": description });
+        let rejects = [
+            json!([]),
+            json!({ "stable-coin": tag("Stablecoin", "Pegged") }),
+            json!({ "stablecoins": tag("Stablecoin", "Pegged") }),
+            json!({ "stablecoin": tag("Stable-coin", "Pegged") }),
+            json!({ "stablecoin": tag(&"a".repeat(22), "Pegged") }),
+            json!({ "stablecoin": tag("Stablecoin", "Pegged!") }),
+            json!({ "stablecoin": tag("Stablecoin", "") }),
+            json!({ "stablecoin": { "name": "Stablecoin" } }),
+            json!({ "stablecoin": { "name": "Stablecoin", "description": "Pegged", "id": 1 } }),
+            (0..21)
+                .map(|i| (format!("tag{i}"), tag("Tag", "A tag")))
+                .collect(),
+        ];
+
+        for tags in rejects {
+            let err = assert_rejects(|list| list.tags = Some(tags));
+            assert!(matches!(err, TokenListError::InvalidList(_)), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_validate_token_fields() {
+        let rejects: [fn(&mut TokenInfo); 10] = [
+            |token| token.chain_id = 0,
+            |token| token.name = String::new(),
+            |token| token.name = "a".repeat(61),
+            |token| token.symbol = "US DC".to_string(),
+            |token| token.symbol = "USDC!".to_string(),
+            |token| token.symbol = "A".repeat(21),
+            |token| token.logo_uri = Some("usdc.png".to_string()),
+            |token| token.tags = Some(vec!["tag".to_string(); 11]),
+            |token| token.tags = Some(vec!["stable-coin".to_string()]),
+            |token| token.tags = Some(vec!["stablecoins".to_string()]),
+        ];
+
+        for change in rejects {
+            assert!(matches!(
+                assert_rejects(|list| change(&mut list.tokens[0])),
+                TokenListError::InvalidToken { address, .. } if address == mainnet::USDC.address
+            ));
+        }
+    }
+
+    #[test]
+    fn test_insert_into_rejected() {
+        let mut list = TokenList::read_json(LIST.as_bytes()).unwrap();
+        let mut clone = list.tokens[0].clone();
+        clone.address = Address::repeat_byte(1);
+        list.tokens.push(clone);
+
+        let mut store = BasicTokenStore::with_conflict_policy(ConflictPolicy::Reject);
+        let rejected = list.insert_into(&mut store);
+
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].0.address, Address::repeat_byte(1));
+        assert!(!store.contains(1, TokenId::Address(Address::repeat_byte(1))));
+    }
+
+    #[test]
+    fn test_from_store() {
+        let previous = TokenList::read_json(LIST.as_bytes()).unwrap();
+        let mut store = BasicTokenStore::new();
+        previous.insert_into(&mut store);
+
+        let unchanged =
+            TokenList::from_store("Test List", &store, &[1, 42161], Some(&previous)).unwrap();
+        assert_eq!(unchanged.version, Version::new(1, 2, 3));
+        assert_eq!(unchanged.tokens, previous.tokens);
+
+        store.insert(1, mainnet::WETH.clone());
+        let added =
+            TokenList::from_store("Test List", &store, &[1, 42161], Some(&previous)).unwrap();
+        assert_eq!(added.version, Version::new(1, 3, 0));
+        assert_eq!(added.tokens[1].name, "WETH");
+
+        let removed = TokenList::from_store("Test List", &store, &[1], Some(&previous)).unwrap();
+        assert_eq!(removed.version, Version::new(2, 0, 0));
+
+        let first = TokenList::from_store("Test List", &store, &[1], None).unwrap();
+        assert_eq!(first.version, Version::new(1, 0, 0));
+
+        let mut roundtrip = Vec::new();
+        first.write_json(&mut roundtrip).unwrap();
+        assert_eq!(TokenList::read_json(roundtrip.as_slice()).unwrap(), first);
+
+        store.insert(1, Token::new(Address::ZERO, "US DC".to_string(), 18));
+        assert!(matches!(
+            TokenList::from_store("Test List", &store, &[1], None),
+            Err(TokenListError::InvalidToken { .. })
+        ));
+        assert!(matches!(
+            TokenList::from_store("Test List", &BasicTokenStore::new(), &[1], None),
+            Err(TokenListError::InvalidSize(0))
+        ));
+    }
+
+    #[test]
+    fn test_version_patch() {
+        let previous = TokenList::read_json(LIST.as_bytes()).unwrap();
+        let mut tokens = previous.tokens.clone();
+        tokens[1] = TokenInfo {
+            logo_uri: Some("https://example.com/usdc.png".to_string()),
+            ..tokens[1].clone()
+        };
+
+        assert_eq!(
+            previous.version.bump(&previous.tokens, &tokens),
+            Version::new(1, 2, 4)
+        );
+        assert_eq!(Version::new(1, 2, 4).to_string(), "1.2.4");
+    }
+
+    #[test]
+    fn test_rfc3339() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(1_709_210_096), "2024-02-29T12:34:56Z");
+        assert!(is_rfc3339(&rfc3339(1_709_210_096)));
+        assert!(is_rfc3339("2024-02-29T12:34:56.789+02:00"));
+        assert!(!is_rfc3339("2024-02-29T12:34:56"));
+        assert!(!is_rfc3339("2024-02-29T12:34:56.Z"));
+        assert!(!is_rfc3339("2024-02-29T12:34:56+0200"));
+    }
+}
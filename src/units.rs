@@ -0,0 +1,320 @@
+use std::cmp::Ordering;
+
+use alloy::primitives::U256;
+use bigdecimal::{
+    num_bigint::{BigInt, Sign},
+    BigDecimal,
+};
+
+use crate::error::InternalError;
+
+/// How to round a decimal amount that has more decimals than its token.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Don't round, and fail on excess precision.
+    #[default]
+    Exact,
+    /// Round towards zero.
+    Down,
+    /// Round away from zero.
+    Up,
+    /// Round to the nearest value, and ties to the even value.
+    HalfEven,
+}
+
+/// Options for formatting a token amount, see [`Token::format_units`].
+///
+/// [`Token::format_units`]: crate::Token::format_units
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    precision: Option<u8>,
+    thousands_separator: Option<char>,
+    symbol: bool,
+}
+
+impl FormatOptions {
+    /// Creates new [`FormatOptions`], formatting every significant decimal,
+    /// without thousands separator nor symbol.
+    pub const fn new() -> Self {
+        Self {
+            precision: None,
+            thousands_separator: None,
+            symbol: false,
+        }
+    }
+
+    /// Formats exactly `precision` decimals, rounding half to even.
+    pub const fn precision(mut self, precision: u8) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Separates the thousands of the integer part with `separator`.
+    pub const fn thousands_separator(mut self, separator: char) -> Self {
+        self.thousands_separator = Some(separator);
+        self
+    }
+
+    /// Appends the token symbol, separated by a space.
+    pub const fn with_symbol(mut self) -> Self {
+        self.symbol = true;
+        self
+    }
+}
+
+/// The number of digits of `U256::MAX`: shifting any non-zero amount by
+/// more overflows.
+const MAX_SHIFT: i64 = 78;
+
+/// Converts `raw` base units of a token with `decimals` to a decimal.
+pub(crate) fn to_decimal(raw: U256, decimals: u8) -> BigDecimal {
+    BigDecimal::from((
+        BigInt::from_bytes_be(Sign::Plus, &raw.to_be_bytes::<{ U256::BYTES }>()),
+        decimals as i64,
+    ))
+}
+
+/// Converts a decimal `value` to base units of a token with `decimals`.
+pub(crate) fn parse_units(
+    value: &BigDecimal,
+    decimals: u8,
+    rounding: Rounding,
+) -> Result<U256, InternalError> {
+    let (digits, scale) = value.as_bigint_and_exponent();
+
+    if digits.sign() == Sign::Minus {
+        return Err(InternalError::InvalidAmount(value.to_string()));
+    }
+
+    let shift = decimals as i64 - scale;
+    let digits = if shift >= 0 {
+        if shift > MAX_SHIFT && digits.sign() != Sign::NoSign {
+            return Err(InternalError::Overflow);
+        }
+        digits * BigInt::from(10).pow(shift.min(MAX_SHIFT) as u32)
+    } else {
+        // Any divisor above the digits rounds the same, from zero or one
+        let exponent = shift
+            .unsigned_abs()
+            .min(digits.to_string().len() as u64 + 1);
+        let divisor = BigInt::from(10).pow(exponent as u32);
+        round(&digits, &divisor, rounding)
+            .ok_or_else(|| InternalError::ExcessPrecision(value.to_string()))?
+    };
+
+    U256::try_from_be_slice(&digits.to_bytes_be().1).ok_or(InternalError::Overflow)
+}
+
+/// Formats `raw` base units of a token with `decimals`.
+pub(crate) fn format_units(
+    raw: U256,
+    decimals: u8,
+    symbol: &str,
+    options: &FormatOptions,
+) -> String {
+    let mut digits = BigInt::from_bytes_be(Sign::Plus, &raw.to_be_bytes::<{ U256::BYTES }>());
+    let mut scale = decimals as u32;
+
+    if let Some(precision) = options.precision {
+        let precision = precision as u32;
+
+        if precision < scale {
+            let divisor = BigInt::from(10).pow(scale - precision);
+            digits = round(&digits, &divisor, Rounding::HalfEven).unwrap_or_default();
+        } else {
+            digits *= BigInt::from(10).pow(precision - scale);
+        }
+        scale = precision;
+    }
+
+    let digits = format!("{:0>width$}", digits, width = scale as usize + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale as usize);
+    let fraction = match options.precision {
+        Some(_) => fraction,
+        None => fraction.trim_end_matches('0'),
+    };
+
+    let mut formatted = match options.thousands_separator {
+        Some(separator) => group_thousands(integer, separator),
+        None => integer.to_string(),
+    };
+
+    if !fraction.is_empty() {
+        formatted.push('.');
+        formatted.push_str(fraction);
+    }
+
+    if options.symbol {
+        formatted.push(' ');
+        formatted.push_str(symbol);
+    }
+
+    formatted
+}
+
+/// Divides `digits` by `divisor` with the given `rounding`, or returns
+/// `None` if the division isn't exact and `rounding` is
+/// [`Rounding::Exact`].
+fn round(digits: &BigInt, divisor: &BigInt, rounding: Rounding) -> Option<BigInt> {
+    let quotient = digits / divisor;
+    let remainder = digits % divisor;
+
+    if remainder == BigInt::ZERO {
+        return Some(quotient);
+    }
+
+    let up = match rounding {
+        Rounding::Exact => return None,
+        Rounding::Down => false,
+        Rounding::Up => true,
+        Rounding::HalfEven => match (remainder * BigInt::from(2)).cmp(divisor) {
+            Ordering::Less => false,
+            Ordering::Greater => true,
+            Ordering::Equal => quotient.bit(0),
+        },
+    };
+
+    Some(if up { quotient + 1 } else { quotient })
+}
+
+fn group_thousands(integer: &str, separator: char) -> String {
+    let mut grouped = String::with_capacity(integer.len() * 4 / 3);
+
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use alloy::primitives::U256;
+    use bigdecimal::BigDecimal;
+
+    use super::{format_units, parse_units, FormatOptions, Rounding};
+    use crate::InternalError;
+
+    fn parse(value: &str, decimals: u8, rounding: Rounding) -> Result<U256, InternalError> {
+        parse_units(&BigDecimal::from_str(value).unwrap(), decimals, rounding)
+    }
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!(
+            parse("1.5", 6, Rounding::Exact).unwrap(),
+            U256::from(1_500_000)
+        );
+        assert_eq!(
+            parse("1e3", 2, Rounding::Exact).unwrap(),
+            U256::from(100_000)
+        );
+        assert_eq!(parse("0", 18, Rounding::Exact).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_parse_units_rounding() {
+        assert!(matches!(
+            parse("1.25", 1, Rounding::Exact),
+            Err(InternalError::ExcessPrecision(_))
+        ));
+        assert_eq!(parse("1.25", 1, Rounding::Down).unwrap(), U256::from(12));
+        assert_eq!(parse("1.21", 1, Rounding::Up).unwrap(), U256::from(13));
+        assert_eq!(
+            parse("1.25", 1, Rounding::HalfEven).unwrap(),
+            U256::from(12)
+        );
+        assert_eq!(
+            parse("1.35", 1, Rounding::HalfEven).unwrap(),
+            U256::from(14)
+        );
+        assert_eq!(
+            parse("1.251", 1, Rounding::HalfEven).unwrap(),
+            U256::from(13)
+        );
+    }
+
+    #[test]
+    fn test_parse_units_errors() {
+        assert!(matches!(
+            parse("-1", 6, Rounding::Down),
+            Err(InternalError::InvalidAmount(_))
+        ));
+        assert!(matches!(
+            parse("1e60", 18, Rounding::Down),
+            Err(InternalError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_parse_units_huge_exponents() {
+        assert!(matches!(
+            parse("1e4294967278", 18, Rounding::Exact),
+            Err(InternalError::Overflow)
+        ));
+        assert_eq!(
+            parse("0e4294967278", 18, Rounding::Exact).unwrap(),
+            U256::ZERO
+        );
+
+        assert!(matches!(
+            parse("1e-4294967314", 18, Rounding::Exact),
+            Err(InternalError::ExcessPrecision(_))
+        ));
+        assert_eq!(
+            parse("1e-4294967314", 18, Rounding::Down).unwrap(),
+            U256::ZERO
+        );
+        assert_eq!(
+            parse("1e-4294967314", 18, Rounding::Up).unwrap(),
+            U256::from(1)
+        );
+        assert_eq!(
+            parse("9e-4294967314", 18, Rounding::HalfEven).unwrap(),
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn test_format_units() {
+        let raw = U256::from(1_234_567_500_000u64);
+
+        assert_eq!(
+            format_units(raw, 6, "USDC", &FormatOptions::new()),
+            "1234567.5"
+        );
+        assert_eq!(
+            format_units(
+                raw,
+                6,
+                "USDC",
+                &FormatOptions::new()
+                    .precision(2)
+                    .thousands_separator(',')
+                    .with_symbol()
+            ),
+            "1,234,567.50 USDC"
+        );
+        assert_eq!(
+            format_units(raw, 6, "USDC", &FormatOptions::new().precision(0)),
+            "1234568"
+        );
+        assert_eq!(
+            format_units(U256::from(5), 6, "USDC", &FormatOptions::new()),
+            "0.000005"
+        );
+        assert_eq!(
+            format_units(U256::from(5), 0, "USDC", &FormatOptions::new().precision(2)),
+            "5.00"
+        );
+        assert_eq!(
+            format_units(U256::from(100), 2, "USDC", &FormatOptions::new()),
+            "1"
+        );
+    }
+}
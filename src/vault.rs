@@ -0,0 +1,329 @@
+use alloy::{
+    network::Network,
+    primitives::{Address, U256},
+    providers::{PendingTransactionBuilder, Provider},
+    sol,
+};
+use async_once_cell::OnceCell;
+use bigdecimal::BigDecimal;
+use std::future::IntoFuture;
+
+use crate::{error::InternalError, Erc20ProviderExt, Error, LazyToken, Token};
+
+sol! {
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IERC4626 {
+        function asset() external view returns (address assetTokenAddress);
+        function totalAssets() external view returns (uint256 totalManagedAssets);
+        function convertToShares(uint256 assets) external view returns (uint256 shares);
+        function convertToAssets(uint256 shares) external view returns (uint256 assets);
+        function maxDeposit(address receiver) external view returns (uint256 maxAssets);
+        function previewDeposit(uint256 assets) external view returns (uint256 shares);
+        function deposit(uint256 assets, address receiver) external returns (uint256 shares);
+        function maxMint(address receiver) external view returns (uint256 maxShares);
+        function previewMint(uint256 shares) external view returns (uint256 assets);
+        function mint(uint256 shares, address receiver) external returns (uint256 assets);
+        function maxWithdraw(address owner) external view returns (uint256 maxAssets);
+        function previewWithdraw(uint256 assets) external view returns (uint256 shares);
+        function withdraw(uint256 assets, address receiver, address owner) external returns (uint256 shares);
+        function maxRedeem(address owner) external view returns (uint256 maxShares);
+        function previewRedeem(uint256 shares) external view returns (uint256 assets);
+        function redeem(uint256 shares, address receiver, address owner) external returns (uint256 assets);
+    }
+}
+
+/// An [ERC-4626] tokenized vault that lazily queries the blockchain.
+///
+/// The vault shares are an ERC-20 token, available through
+/// [`LazyVault::token`]. Every method fails with an [`Error`] identifying
+/// the vault by address.
+///
+/// [ERC-4626]: https://eips.ethereum.org/EIPS/eip-4626
+#[derive(Debug)]
+pub struct LazyVault<P, N> {
+    token: LazyToken<P, N>,
+    asset: OnceCell<Address>,
+    asset_token: OnceCell<Token>,
+}
+
+impl<P, N> LazyVault<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Creates a new [`LazyVault`].
+    pub const fn new(address: Address, provider: P) -> Self {
+        Self {
+            token: LazyToken::new(address, provider),
+            asset: OnceCell::new(),
+            asset_token: OnceCell::new(),
+        }
+    }
+
+    /// Returns the vault contract address.
+    pub const fn address(&self) -> &Address {
+        self.token.address()
+    }
+
+    /// Returns the provider used to query the vault.
+    pub const fn provider(&self) -> &P {
+        self.token.provider()
+    }
+
+    /// Returns the vault shares token.
+    pub const fn token(&self) -> &LazyToken<P, N> {
+        &self.token
+    }
+
+    /// Returns the address of the underlying asset.
+    pub async fn asset(&self) -> Result<&Address, Error> {
+        self.asset
+            .get_or_try_init(self.instance().asset().call().into_future())
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the underlying asset token.
+    pub async fn asset_token(&self) -> Result<&Token, Error> {
+        self.asset_token
+            .get_or_try_init(async {
+                let asset = self.asset().await?;
+
+                self.provider().retrieve_token(*asset).await
+            })
+            .await
+    }
+
+    /// Returns the amount of underlying assets managed by the vault.
+    pub async fn total_assets(&self) -> Result<U256, Error> {
+        self.instance()
+            .totalAssets()
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the amount of shares exchanged for `assets`, in an ideal
+    /// scenario without fees nor slippage.
+    pub async fn convert_to_shares(&self, assets: U256) -> Result<U256, Error> {
+        self.instance()
+            .convertToShares(assets)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the amount of assets exchanged for `shares`, in an ideal
+    /// scenario without fees nor slippage.
+    pub async fn convert_to_assets(&self, shares: U256) -> Result<U256, Error> {
+        self.instance()
+            .convertToAssets(shares)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the amount of shares minted by depositing `assets` now.
+    pub async fn preview_deposit(&self, assets: U256) -> Result<U256, Error> {
+        self.instance()
+            .previewDeposit(assets)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the amount of assets needed to mint `shares` now.
+    pub async fn preview_mint(&self, shares: U256) -> Result<U256, Error> {
+        self.instance()
+            .previewMint(shares)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the amount of shares burned by withdrawing `assets` now.
+    pub async fn preview_withdraw(&self, assets: U256) -> Result<U256, Error> {
+        self.instance()
+            .previewWithdraw(assets)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the amount of assets withdrawn by redeeming `shares` now.
+    pub async fn preview_redeem(&self, shares: U256) -> Result<U256, Error> {
+        self.instance()
+            .previewRedeem(shares)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the maximum amount of assets that can be deposited for
+    /// `receiver`.
+    pub async fn max_deposit(&self, receiver: Address) -> Result<U256, Error> {
+        self.instance()
+            .maxDeposit(receiver)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the maximum amount of shares that can be minted for
+    /// `receiver`.
+    pub async fn max_mint(&self, receiver: Address) -> Result<U256, Error> {
+        self.instance()
+            .maxMint(receiver)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the maximum amount of assets that `owner` can withdraw.
+    pub async fn max_withdraw(&self, owner: Address) -> Result<U256, Error> {
+        self.instance()
+            .maxWithdraw(owner)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Returns the maximum amount of shares that `owner` can redeem.
+    pub async fn max_redeem(&self, owner: Address) -> Result<U256, Error> {
+        self.instance()
+            .maxRedeem(owner)
+            .call()
+            .await
+            .map_err(|err| self.error(err))
+    }
+
+    /// Values `shares` in underlying assets, as a [`BigDecimal`] with the
+    /// asset decimals.
+    ///
+    /// See [`LazyVault::convert_to_assets`].
+    pub async fn share_value(&self, shares: U256) -> Result<BigDecimal, Error> {
+        let asset = self.asset_token().await?;
+        let assets = self.convert_to_assets(shares).await?;
+
+        Ok(asset.get_balance(assets))
+    }
+
+    /// Values the shares owned by `account` in underlying assets, see
+    /// [`LazyVault::share_value`].
+    pub async fn balance_value(&self, account: Address) -> Result<BigDecimal, Error> {
+        let shares = self.token.balance_of(account).await?;
+
+        self.share_value(shares).await
+    }
+
+    /// Wraps an error from a vault call.
+    fn error(&self, err: impl Into<InternalError>) -> Error {
+        Error::new((*self.address()).into(), err)
+    }
+
+    const fn instance(&self) -> IERC4626::IERC4626Instance<&P, N> {
+        IERC4626::new(*self.address(), self.provider())
+    }
+}
+
+/// An [ERC-4626] tokenized vault with deposits and withdrawals support,
+/// using a signer-capable provider.
+///
+/// Reads are available through [`LazyVaultSigner::vault`]. The underlying
+/// assets must be approved to the vault before depositing, see
+/// [`LazyTokenSigner::approve`].
+///
+/// [ERC-4626]: https://eips.ethereum.org/EIPS/eip-4626
+/// [`LazyTokenSigner::approve`]: crate::LazyTokenSigner::approve
+#[derive(Debug)]
+pub struct LazyVaultSigner<P, N> {
+    vault: LazyVault<P, N>,
+}
+
+impl<P, N> LazyVaultSigner<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    /// Creates a new [`LazyVaultSigner`].
+    pub const fn new(address: Address, provider: P) -> Self {
+        Self {
+            vault: LazyVault::new(address, provider),
+        }
+    }
+
+    /// Returns the vault contract address.
+    pub const fn address(&self) -> &Address {
+        self.vault.address()
+    }
+
+    /// Returns the vault, to query it.
+    pub const fn vault(&self) -> &LazyVault<P, N> {
+        &self.vault
+    }
+
+    /// Deposits `assets` from the caller, minting shares to `receiver`.
+    pub async fn deposit(
+        &self,
+        assets: U256,
+        receiver: Address,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.vault
+            .instance()
+            .deposit(assets, receiver)
+            .send()
+            .await
+            .map_err(|err| self.vault.error(err))
+    }
+
+    /// Mints exactly `shares` to `receiver`, depositing the needed assets
+    /// from the caller.
+    pub async fn mint(
+        &self,
+        shares: U256,
+        receiver: Address,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.vault
+            .instance()
+            .mint(shares, receiver)
+            .send()
+            .await
+            .map_err(|err| self.vault.error(err))
+    }
+
+    /// Withdraws exactly `assets` to `receiver`, burning the needed shares
+    /// from `owner`.
+    ///
+    /// The caller must be `owner`, or have an allowance over its shares.
+    pub async fn withdraw(
+        &self,
+        assets: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.vault
+            .instance()
+            .withdraw(assets, receiver, owner)
+            .send()
+            .await
+            .map_err(|err| self.vault.error(err))
+    }
+
+    /// Redeems `shares` from `owner`, withdrawing the assets to `receiver`.
+    ///
+    /// The caller must be `owner`, or have an allowance over its shares.
+    pub async fn redeem(
+        &self,
+        shares: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<PendingTransactionBuilder<N>, Error> {
+        self.vault
+            .instance()
+            .redeem(shares, receiver, owner)
+            .send()
+            .await
+            .map_err(|err| self.vault.error(err))
+    }
+}
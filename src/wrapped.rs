@@ -0,0 +1,126 @@
+use alloy::{
+    network::Network,
+    primitives::{Address, U256},
+    providers::{PendingTransactionBuilder, Provider},
+    sol,
+};
+
+use crate::{
+    arbitrum, avalanche, base, bsc, gnosis, linea, mainnet, optimism, polygon, scroll, sepolia,
+    zksync, Error, LazyTokenSigner, Token,
+};
+
+sol! {
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IWETH {
+        function deposit() external payable;
+        function withdraw(uint256 wad) external;
+    }
+}
+
+/// Returns the canonical wrapped native token of a chain, like WETH on
+/// Ethereum or WBNB on BNB Smart Chain.
+pub fn wrapped_native(chain_id: u64) -> Option<&'static Token> {
+    let token = match chain_id {
+        1 => &mainnet::WETH,
+        10 => &optimism::WETH,
+        56 => &bsc::WBNB,
+        100 => &gnosis::WXDAI,
+        137 => &polygon::WPOL,
+        324 => &zksync::WETH,
+        8453 => &base::WETH,
+        42161 => &arbitrum::WETH,
+        43114 => &avalanche::WAVAX,
+        59144 => &linea::WETH,
+        534352 => &scroll::WETH,
+        11155111 => &sepolia::WETH,
+        _ => return None,
+    };
+
+    Some(&**token)
+}
+
+/// A WETH-style wrapped native token, wrapping and unwrapping the native
+/// asset of a chain.
+///
+/// The wrapped token is available through [`WrappedNative::token`].
+#[derive(Debug)]
+pub struct WrappedNative<P, N>
+where
+    P: Provider<N>,
+    N: Network,
+{
+    token: LazyTokenSigner<P, N>,
+}
+
+impl<P, N> WrappedNative<P, N>
+where
+    P: Provider<N> + Clone,
+    N: Network,
+{
+    /// Creates a new [`WrappedNative`] at the given address.
+    pub fn new(address: Address, provider: P) -> Self {
+        Self {
+            token: LazyTokenSigner::new(address, provider),
+        }
+    }
+
+    /// Creates a new [`WrappedNative`] at the canonical address of the
+    /// chain, see [`wrapped_native`].
+    pub fn for_chain(chain_id: u64, provider: P) -> Option<Self> {
+        wrapped_native(chain_id).map(|token| Self::new(token.address, provider))
+    }
+
+    /// Returns the wrapped token contract address.
+    pub const fn address(&self) -> &Address {
+        self.token.address()
+    }
+
+    /// Returns the wrapped token.
+    pub const fn token(&self) -> &LazyTokenSigner<P, N> {
+        &self.token
+    }
+
+    /// Wraps `amount` of the native asset, crediting the caller with as
+    /// many wrapped tokens.
+    pub async fn deposit(&self, amount: U256) -> Result<PendingTransactionBuilder<N>, Error> {
+        IWETH::new(*self.address(), self.token.provider())
+            .deposit()
+            .value(amount)
+            .send()
+            .await
+            .map_err(|err| self.token.token.error(err))
+    }
+
+    /// Unwraps `amount` of wrapped tokens of the caller, sending it as
+    /// much of the native asset.
+    pub async fn withdraw(&self, amount: U256) -> Result<PendingTransactionBuilder<N>, Error> {
+        IWETH::new(*self.address(), self.token.provider())
+            .withdraw(amount)
+            .send()
+            .await
+            .map_err(|err| self.token.token.error(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wrapped_native;
+    use crate::{bsc, mainnet, native_token};
+
+    #[test]
+    fn test_wrapped_native() {
+        assert_eq!(wrapped_native(1).unwrap().address, mainnet::WETH.address);
+        assert_eq!(wrapped_native(56).unwrap().address, bsc::WBNB.address);
+        assert!(wrapped_native(31337).is_none());
+
+        for chain_id in [1, 10, 56, 100, 137, 43114] {
+            let wrapped = wrapped_native(chain_id).unwrap();
+            let native = native_token(chain_id);
+
+            assert!(wrapped.symbol.ends_with(&native.symbol), "chain {chain_id}");
+            assert_eq!(wrapped.decimals, native.decimals);
+        }
+    }
+}
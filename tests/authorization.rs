@@ -0,0 +1,164 @@
+mod common;
+
+use alloy::primitives::{B256, U256};
+use alloy_erc20::LazyTokenSigner;
+use common::{
+    derive_anvil_signer, TestContext, ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, ONE_TOKEN, TEN_TOKENS,
+};
+
+// The test token recovers the signer with the `ecrecover` precompile, which
+// only accepts a `v` of 27 or 28: accepted authorizations prove the signature
+// encoding.
+
+#[tokio::test]
+async fn test_transfer_with_authorization() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_fiat_token("2").await;
+    ctx.mint_tokens(token_address, ANVIL_ADDRESS_0, U256::from(TEN_TOKENS))
+        .await;
+
+    let owner = derive_anvil_signer(0);
+    let relayer = LazyTokenSigner::new(token_address, ctx.create_provider_with_signer(2));
+    let nonce = B256::repeat_byte(1);
+
+    assert!(!relayer
+        .authorization_state(ANVIL_ADDRESS_0, nonce)
+        .await
+        .unwrap());
+
+    let signed = relayer
+        .sign_transfer_authorization(
+            &owner,
+            ANVIL_ADDRESS_1,
+            U256::from(ONE_TOKEN),
+            U256::ZERO,
+            U256::MAX,
+            nonce,
+        )
+        .await
+        .unwrap();
+    relayer
+        .transfer_with_authorization(&signed)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    assert!(relayer
+        .authorization_state(ANVIL_ADDRESS_0, nonce)
+        .await
+        .unwrap());
+    assert_eq!(
+        relayer.balance_of(ANVIL_ADDRESS_1).await.unwrap(),
+        U256::from(ONE_TOKEN)
+    );
+
+    // The nonce is used up, so the authorization can't be replayed
+    assert!(relayer.transfer_with_authorization(&signed).await.is_err());
+}
+
+#[tokio::test]
+async fn test_receive_with_authorization() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_fiat_token("2").await;
+    ctx.mint_tokens(token_address, ANVIL_ADDRESS_0, U256::from(TEN_TOKENS))
+        .await;
+
+    let owner = derive_anvil_signer(0);
+    let relayer = LazyTokenSigner::new(token_address, ctx.create_provider_with_signer(2));
+    let recipient = LazyTokenSigner::new(token_address, ctx.create_provider_with_signer(1));
+    let nonce = B256::repeat_byte(2);
+
+    let signed = recipient
+        .sign_receive_authorization(
+            &owner,
+            ANVIL_ADDRESS_1,
+            U256::from(ONE_TOKEN),
+            U256::ZERO,
+            U256::MAX,
+            nonce,
+        )
+        .await
+        .unwrap();
+
+    // Only the recipient can submit it
+    assert!(relayer.receive_with_authorization(&signed).await.is_err());
+    assert!(!recipient
+        .authorization_state(ANVIL_ADDRESS_0, nonce)
+        .await
+        .unwrap());
+
+    recipient
+        .receive_with_authorization(&signed)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    assert!(recipient
+        .authorization_state(ANVIL_ADDRESS_0, nonce)
+        .await
+        .unwrap());
+    assert_eq!(
+        recipient.balance_of(ANVIL_ADDRESS_1).await.unwrap(),
+        U256::from(ONE_TOKEN)
+    );
+}
+
+#[tokio::test]
+async fn test_cancel_authorization() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_fiat_token("2").await;
+    ctx.mint_tokens(token_address, ANVIL_ADDRESS_0, U256::from(TEN_TOKENS))
+        .await;
+
+    let owner = derive_anvil_signer(0);
+    let relayer = LazyTokenSigner::new(token_address, ctx.create_provider_with_signer(2));
+    let nonce = B256::repeat_byte(3);
+
+    let transfer = relayer
+        .sign_transfer_authorization(
+            &owner,
+            ANVIL_ADDRESS_1,
+            U256::from(ONE_TOKEN),
+            U256::ZERO,
+            U256::MAX,
+            nonce,
+        )
+        .await
+        .unwrap();
+    let cancel = relayer
+        .sign_cancel_authorization(&owner, nonce)
+        .await
+        .unwrap();
+
+    assert!(!relayer
+        .authorization_state(ANVIL_ADDRESS_0, nonce)
+        .await
+        .unwrap());
+
+    relayer
+        .cancel_authorization(&cancel)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    assert!(relayer
+        .authorization_state(ANVIL_ADDRESS_0, nonce)
+        .await
+        .unwrap());
+
+    // The canceled authorization can't be used anymore
+    assert!(relayer
+        .transfer_with_authorization(&transfer)
+        .await
+        .is_err());
+    assert_eq!(
+        relayer.balance_of(ANVIL_ADDRESS_1).await.unwrap(),
+        U256::ZERO
+    );
+}
@@ -1,12 +1,16 @@
 use alloy::{
     hex,
-    network::EthereumWallet,
+    network::{EthereumWallet, TransactionBuilder},
     primitives::{address, Address, FixedBytes, U256},
-    providers::{Provider, ProviderBuilder, RootProvider},
+    providers::{
+        bindings::IMulticall3, Provider, ProviderBuilder, RootProvider, MULTICALL3_ADDRESS,
+    },
+    rpc::types::TransactionRequest,
     signers::local::PrivateKeySigner,
     sol,
     transports::http::Http,
 };
+use alloy_provider::ext::AnvilApi;
 use alloy_rpc_client::RpcClient;
 use testcontainers_modules::{
     anvil::AnvilNode,
@@ -20,7 +24,7 @@ use testcontainers_modules::{
 ///
 /// These are public knowledge test keys documented at:
 /// https://book.getfoundry.sh/reference/anvil/
-fn derive_anvil_signer(index: u32) -> PrivateKeySigner {
+pub fn derive_anvil_signer(index: u32) -> PrivateKeySigner {
     let key_hex = match index {
         0 => {
             // Account 0: 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
@@ -88,6 +92,31 @@ sol! {
         function approve(address spender, uint256 amount) external returns (bool);
         function transferFrom(address from, address to, uint256 amount) external returns (bool);
     }
+
+    /// A WETH9-like wrapped Ether, whose total supply is its Ether balance,
+    /// and whose fallback deposits the received Ether.
+    #[sol(rpc, bytecode = "6104ab61000d5f396104ab5ff35f3560e01c806306fdde03146100c557806395d89b41146100f8578063313ce5671461012b57806318160ddd1461013557806370a082311461013e578063dd62ed3e14610155578063095ea7b314610178578063a9059cbb146101e957806323b872dd1461029d578063d0e30db0146103ec5780632e1a7d4d146104345750346001335f5260205260405f2054016001335f5260205260405f2055345f52337fe1fffcc4923d04b559f4d29a8bfc6cda04eb5b0d3c460751c2402c5c5cc9109c60205fa2005b5060205f52600d6020527f577261707065642045746865720000000000000000000000000000000000000060405260605ff35b5060205f5260046020527f574554480000000000000000000000000000000000000000000000000000000060405260605ff35b5060125f5260205ff35b50475f5260205ff35b5060016004355f5260205260405f20545f5260205ff35b5060026004355f5260205260405f206024355f5260205260405f20545f5260205ff35b5033610200526004356102205260243561024052610240516002610200515f5260205260405f20610220515f5260205260405f2055610240515f5261022051610200517f8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b92560205fa360015f5260205ff35b503361026052600435610280526024356102a0526001610260515f5260205260405f20546102c0526102a0516102c0511015610223575f5ffd5b6102a0516102c051036001610260515f5260205260405f20556102a0516001610280515f5260205260405f2054016001610280515f5260205260405f20556102a0515f5261028051610260517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa360015f5260205ff35b506004356102e05233610300526044356103205260026102e0515f5260205260405f20610300515f5260205260405f205461034052610320516103405110156102e4575f5ffd5b7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff610340511415156103375761032051610340510360026102e0515f5260205260405f20610300515f5260205260405f20555b60043561026052602435610280526044356102a0526001610260515f5260205260405f20546102c0526102a0516102c0511015610372575f5ffd5b6102a0516102c051036001610260515f5260205260405f20556102a0516001610280515f5260205260405f2054016001610280515f5260205260405f20556102a0515f5261028051610260517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa360015f5260205ff35b50346001335f5260205260405f2054016001335f5260205260405f2055345f52337fe1fffcc4923d04b559f4d29a8bfc6cda04eb5b0d3c460751c2402c5c5cc9109c60205fa2005b506001335f5260205260405f205461020052600435610200511015610457575f5ffd5b60043561020051036001335f5260205260405f20555f5f5f5f600435335af161047e575f5ffd5b6004355f52337f7fcf532c15f0a6db0bd6d0e038bea71d30d808c7d98cb3bf7268a95bf5081b6560205fa200")]
+    contract WETH9 {
+        function deposit() external payable;
+        function withdraw(uint256 wad) external;
+    }
+
+    /// A USDC-like token named "Fiat Token", with EIP-2612 permits and
+    /// EIP-3009 authorizations, whose EIP-712 domain version is given at
+    /// deployment. It doesn't implement ERC-5267.
+    #[sol(rpc, bytecode = "610e2f3803610e2f5f395f515160205f510120600555610e0c6100235f39610e0c5ff35f3560e01c806306fdde03146100c457806395d89b41146100f7578063313ce5671461012a57806318160ddd1461013457806370a082311461013e578063dd62ed3e14610155578063095ea7b314610178578063a9059cbb146101e957806323b872dd1461029d57806340c10f19146103ec5780637ecebe001461045b5780633644e51514610472578063d505accf146104db578063e94a0102146106d3578063e3ee160e146106f6578063ef55bec61461099a5780635a049a7014610c4b57505f5ffd5b5060205f52600a6020527f4669617420546f6b656e0000000000000000000000000000000000000000000060405260605ff35b5060205f5260046020527f464941540000000000000000000000000000000000000000000000000000000060405260605ff35b5060125f5260205ff35b505f545f5260205ff35b5060016004355f5260205260405f20545f5260205ff35b5060026004355f5260205260405f206024355f5260205260405f20545f5260205ff35b5033610200526004356102205260243561024052610240516002610200515f5260205260405f20610220515f5260205260405f2055610240515f5261022051610200517f8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b92560205fa360015f5260205ff35b503361026052600435610280526024356102a0526001610260515f5260205260405f20546102c0526102a0516102c0511015610223575f5ffd5b6102a0516102c051036001610260515f5260205260405f20556102a0516001610280515f5260205260405f2054016001610280515f5260205260405f20556102a0515f5261028051610260517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa360015f5260205ff35b506004356102e05233610300526044356103205260026102e0515f5260205260405f20610300515f5260205260405f205461034052610320516103405110156102e4575f5ffd5b7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff610340511415156103375761032051610340510360026102e0515f5260205260405f20610300515f5260205260405f20555b60043561026052602435610280526044356102a0526001610260515f5260205260405f20546102c0526102a0516102c0511015610372575f5ffd5b6102a0516102c051036001610260515f5260205260405f20556102a0516001610280515f5260205260405f2054016001610280515f5260205260405f20556102a0515f5261028051610260517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa360015f5260205ff35b506004356103405260243561036052610360515f54015f55610360516001610340515f5260205260405f2054016001610340515f5260205260405f2055610360515f52610340515f7fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa3005b5060036004355f5260205260405f20545f5260205ff35b507f8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f610400527fb4b73d95aa5551c5001f7a811ba5adb960777e759565c6d9a73e9214d01da49661042052600554610440524661046052306104805260a0610400205f5260205ff35b506064354211156104ea575f5ffd5b60036004355f5260205260405f2054610200526001610200510160036004355f5260205260405f20557f6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c96104005260043561042052602435610440526044356104605261020051610480526064356104a05260c061040020610220527f8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f610400527fb4b73d95aa5551c5001f7a811ba5adb960777e759565c6d9a73e9214d01da49661042052600554610440524661046052306104805260a061040020610240527f190100000000000000000000000000000000000000000000000000000000000061040052610240516104025261022051610422526042610400206102605261026051610400526084356104205260a4356104405260c435610460525f610480526020610480608061040060015afa610642575f5ffd5b6104805161028052610280511515610658575f5ffd5b6004356102805114610668575f5ffd5b6004356102a0526024356102c0526044356102e0526102e05160026102a0515f5260205260405f206102c0515f5260205260405f20556102e0515f526102c0516102a0517f8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b92560205fa3005b5060046004355f5260205260405f206024355f5260205260405f20545f5260205ff35b506064354211610704575f5ffd5b6084354210610711575f5ffd5b7f7c7c6cdb67a18743f49ec6fa9b35f50d52ed05cbed4cc592e13b44501c1a226761040052600435610420526024356104405260443561046052606435610480526084356104a05260a4356104c05260e061040020610200527f8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f610400527fb4b73d95aa5551c5001f7a811ba5adb960777e759565c6d9a73e9214d01da49661042052600554610440524661046052306104805260a061040020610220527f1901000000000000000000000000000000000000000000000000000000000000610400526102205161040252610200516104225260426104002061024052610240516104005260c4356104205260e4356104405261010435610460525f610480526020610480608061040060015afa610847575f5ffd5b610480516102605261026051151561085d575f5ffd5b600435610260511461086d575f5ffd5b6004356102805260a4356102a0526004610280515f5260205260405f206102a0515f5260205260405f2054156108a1575f5ffd5b60016004610280515f5260205260405f206102a0515f5260205260405f20556102a051610280517f98de503528ee59b575ef0c0a2576a82497bfc029a5685b209e9ec333479b10a55f5fa36004356102c0526024356102e0526044356103005260016102c0515f5260205260405f20546103205261030051610320511015610927575f5ffd5b61030051610320510360016102c0515f5260205260405f20556103005160016102e0515f5260205260405f20540160016102e0515f5260205260405f2055610300515f526102e0516102c0517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa3005b5060643542116109a8575f5ffd5b60843542106109b5575f5ffd5b33602435146109c2575f5ffd5b7fd099cc98ef71107a616c4f0f941f04c322d8e254fe26b3c6668db87aae413de861040052600435610420526024356104405260443561046052606435610480526084356104a05260a4356104c05260e061040020610200527f8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f610400527fb4b73d95aa5551c5001f7a811ba5adb960777e759565c6d9a73e9214d01da49661042052600554610440524661046052306104805260a061040020610220527f1901000000000000000000000000000000000000000000000000000000000000610400526102205161040252610200516104225260426104002061024052610240516104005260c4356104205260e4356104405261010435610460525f610480526020610480608061040060015afa610af8575f5ffd5b6104805161026052610260511515610b0e575f5ffd5b6004356102605114610b1e575f5ffd5b6004356102805260a4356102a0526004610280515f5260205260405f206102a0515f5260205260405f205415610b52575f5ffd5b60016004610280515f5260205260405f206102a0515f5260205260405f20556102a051610280517f98de503528ee59b575ef0c0a2576a82497bfc029a5685b209e9ec333479b10a55f5fa36004356102c0526024356102e0526044356103005260016102c0515f5260205260405f20546103205261030051610320511015610bd8575f5ffd5b61030051610320510360016102c0515f5260205260405f20556103005160016102e0515f5260205260405f20540160016102e0515f5260205260405f2055610300515f526102e0516102c0517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa3005b507f158b0a9edf7a828aad02f63cd515c68ef2f50ba807396f6d12842833a1597429610400526004356104205260243561044052606061040020610220527f8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f610400527fb4b73d95aa5551c5001f7a811ba5adb960777e759565c6d9a73e9214d01da49661042052600554610440524661046052306104805260a061040020610240527f190100000000000000000000000000000000000000000000000000000000000061040052610240516104025261022051610422526042610400206102605261026051610400526044356104205260643561044052608435610460525f610480526020610480608061040060015afa610d65575f5ffd5b6104805161028052610280511515610d7b575f5ffd5b6004356102805114610d8b575f5ffd5b60043561030052602435610320526004610300515f5260205260405f20610320515f5260205260405f205415610dbf575f5ffd5b60016004610300515f5260205260405f20610320515f5260205260405f205561032051610300517f1cdd46ff242716cdaa72d159d339a485b3438398348d68f09d7c8c0a59353d815f5fa300")]
+    contract FiatToken {
+        constructor(string version);
+
+        function mint(address to, uint256 amount) external;
+    }
+
+    /// An ERC-4626 vault over `asset`, whose shares are worth the vault asset
+    /// balance.
+    #[sol(rpc, bytecode = "602061106b5f395f516005556110526100195f396110525ff35f3560e01c806306fdde031461011c57806395d89b411461014f578063313ce5671461018257806318160ddd1461018c57806370a0823114610196578063dd62ed3e146101ad578063095ea7b3146101d0578063a9059cbb1461024157806323b872dd146102f557806338d52e0f1461044457806301e1d1141461044f578063c6e6f592146104a157806307a2d13a14610528578063402d267d146105af578063c63d75b6146105d8578063ce96cb7714610601578063d905777e14610694578063ef8b30f7146106ab578063b3d7f6b9146107325780630a28a477146107bf5780634cdad5061461084e5780636e553f65146108d557806394bf804d14610a51578063b460af9414610bd3578063ba08765214610e1757505f5ffd5b5060205f52600a6020527f54657374205661756c740000000000000000000000000000000000000000000060405260605ff35b5060205f5260056020527f765445535400000000000000000000000000000000000000000000000000000060405260605ff35b5060125f5260205ff35b505f545f5260205ff35b5060016004355f5260205260405f20545f5260205ff35b5060026004355f5260205260405f206024355f5260205260405f20545f5260205ff35b5033610200526004356102205260243561024052610240516002610200515f5260205260405f20610220515f5260205260405f2055610240515f5261022051610200517f8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b92560205fa360015f5260205ff35b503361026052600435610280526024356102a0526001610260515f5260205260405f20546102c0526102a0516102c051101561027b575f5ffd5b6102a0516102c051036001610260515f5260205260405f20556102a0516001610280515f5260205260405f2054016001610280515f5260205260405f20556102a0515f5261028051610260517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa360015f5260205ff35b506004356102e05233610300526044356103205260026102e0515f5260205260405f20610300515f5260205260405f2054610340526103205161034051101561033c575f5ffd5b7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff6103405114151561038f5761032051610340510360026102e0515f5260205260405f20610300515f5260205260405f20555b60043561026052602435610280526044356102a0526001610260515f5260205260405f20546102c0526102a0516102c05110156103ca575f5ffd5b6102a0516102c051036001610260515f5260205260405f20556102a0516001610280515f5260205260405f2054016001610280515f5260205260405f20556102a0515f5261028051610260517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa360015f5260205ff35b506005545f5260205ff35b507f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa610496575f5ffd5b610180515f5260205ff35b50600435610200525f5415156104be57610200516102205261051d565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa610504575f5ffd5b6101805161024052610240515f54610200510204610220525b610220515f5260205ff35b50600435610260525f5415156105455761026051610280526105a4565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa61058b575f5ffd5b610180516102a0525f546102a051610260510204610280525b610280515f5260205ff35b507fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff5f5260205ff35b507fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff5f5260205ff35b5060016004355f5260205260405f2054610260525f54151561062a576102605161028052610689565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa610670575f5ffd5b610180516102a0525f546102a051610260510204610280525b610280515f5260205ff35b5060016004355f5260205260405f20545f5260205ff35b50600435610200525f5415156106c8576102005161022052610727565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa61070e575f5ffd5b6101805161024052610240515f54610200510204610220525b610220515f5260205ff35b50600435610260525f54151561074f5761026051610280526107b4565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa610795575f5ffd5b610180516102a0525f5460015f54036102a05161026051020104610280525b610280515f5260205ff35b50600435610200525f5415156107dc576102005161022052610843565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa610822575f5ffd5b610180516102405261024051600161024051035f5461020051020104610220525b610220515f5260205ff35b50600435610260525f54151561086b5761026051610280526108ca565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa6108b1575f5ffd5b610180516102a0525f546102a051610260510204610280525b610280515f5260205ff35b50600435610200525f5415156108f2576102005161022052610951565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa610938575f5ffd5b6101805161024052610240515f54610200510204610220525b7f23b872dd000000000000000000000000000000000000000000000000000000006101005233610104523061012452600435610144525f61018052602061018060646101005f6005545af16109a4575f5ffd5b6024356102c052610220516102e0526102e0515f54015f556102e05160016102c0515f5260205260405f20540160016102c0515f5260205260405f20556102e0515f526102c0515f7fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa36004355f5261022051602052602435337fdcbc1c05240f31ff3ad067ef1ee35ce4997762752e3a095284754544f4c709d760405fa3610220515f5260205ff35b50600435610260525f541515610a6e576102605161028052610ad3565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa610ab4575f5ffd5b610180516102a0525f5460015f54036102a05161026051020104610280525b7f23b872dd00000000000000000000000000000000000000000000000000000000610100523361010452306101245261028051610144525f61018052602061018060646101005f6005545af1610b27575f5ffd5b6024356102c0526004356102e0526102e0515f54015f556102e05160016102c0515f5260205260405f20540160016102c0515f5260205260405f20556102e0515f526102c0515f7fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa3610280515f52600435602052602435337fdcbc1c05240f31ff3ad067ef1ee35ce4997762752e3a095284754544f4c709d760405fa3610280515f5260205ff35b50600435610200525f541515610bf0576102005161022052610c57565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa610c36575f5ffd5b610180516102405261024051600161024051035f5461020051020104610220525b60443533141515610cfd5760443561030052336103205261022051610340526002610300515f5260205260405f20610320515f5260205260405f20546103605261034051610360511015610ca9575f5ffd5b7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff61036051141515610cfc576103405161036051036002610300515f5260205260405f20610320515f5260205260405f20555b5b60443561038052610220516103a0526001610380515f5260205260405f20546103c0526103a0516103c0511015610d32575f5ffd5b6103a0516103c051036001610380515f5260205260405f20556103a0515f54035f556103a0515f525f610380517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa37fa9059cbb000000000000000000000000000000000000000000000000000000006101005260243561010452600435610124525f61018052602061018060446101005f6005545af1610dd4575f5ffd5b6004355f5261022051602052604435602435337ffbde797d201c681b91056529119e0b02407c7bb96a4a2c75c01fc9667232c8db60405fa4610220515f5260205ff35b50600435610260525f541515610e34576102605161028052610e93565b7f70a08231000000000000000000000000000000000000000000000000000000006101005230610104525f61018052602061018060246101006005545afa610e7a575f5ffd5b610180516102a0525f546102a051610260510204610280525b60443533141515610f3857604435610300523361032052600435610340526002610300515f5260205260405f20610320515f5260205260405f20546103605261034051610360511015610ee4575f5ffd5b7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff61036051141515610f37576103405161036051036002610300515f5260205260405f20610320515f5260205260405f20555b5b604435610380526004356103a0526001610380515f5260205260405f20546103c0526103a0516103c0511015610f6c575f5ffd5b6103a0516103c051036001610380515f5260205260405f20556103a0515f54035f556103a0515f525f610380517fddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef60205fa37fa9059cbb00000000000000000000000000000000000000000000000000000000610100526024356101045261028051610124525f61018052602061018060446101005f6005545af161100f575f5ffd5b610280515f52600435602052604435602435337ffbde797d201c681b91056529119e0b02407c7bb96a4a2c75c01fc9667232c8db60405fa4610280515f5260205ff3")]
+    contract Vault {
+        constructor(address asset);
+    }
 }
 
 /// Test environment context containing the Anvil container and endpoint
@@ -131,6 +160,7 @@ impl TestContext {
     }
 
     /// Deploys a test ERC-20 token and returns its address
+    #[allow(dead_code)]
     pub async fn deploy_token(&self) -> Address {
         let provider = self.create_provider_with_signer(0);
         let contract = SimpleERC20::deploy(&provider).await.unwrap();
@@ -138,6 +168,7 @@ impl TestContext {
     }
 
     /// Deploys a token and mints the specified amount to an address
+    #[allow(dead_code)]
     pub async fn deploy_and_mint(&self, to: Address, amount: U256) -> Address {
         let token_address = self.deploy_token().await;
         self.mint_tokens(token_address, to, amount).await;
@@ -158,4 +189,56 @@ impl TestContext {
             .await
             .unwrap();
     }
+
+    /// Deploys a WETH9-like wrapped Ether and returns its address
+    #[allow(dead_code)]
+    pub async fn deploy_weth9(&self) -> Address {
+        let provider = self.create_provider_with_signer(0);
+        let contract = WETH9::deploy(&provider).await.unwrap();
+        *contract.address()
+    }
+
+    /// Deploys a token with EIP-2612 permits and EIP-3009 authorizations,
+    /// whose EIP-712 domain has the given version, and returns its address
+    #[allow(dead_code)]
+    pub async fn deploy_fiat_token(&self, version: &str) -> Address {
+        let provider = self.create_provider_with_signer(0);
+        let contract = FiatToken::deploy(&provider, version.to_string())
+            .await
+            .unwrap();
+        *contract.address()
+    }
+
+    /// Deploys an ERC-4626 vault over `asset` and returns its address
+    #[allow(dead_code)]
+    pub async fn deploy_vault(&self, asset: Address) -> Address {
+        let provider = self.create_provider_with_signer(0);
+        let contract = Vault::deploy(&provider, asset).await.unwrap();
+        *contract.address()
+    }
+
+    /// Installs Multicall3 at its canonical address, as Anvil doesn't ship
+    /// with it unless forking
+    #[allow(dead_code)]
+    pub async fn deploy_multicall3(&self) {
+        let provider = self.create_provider_with_signer(0);
+        let tx = TransactionRequest::default().with_deploy_code(IMulticall3::BYTECODE.clone());
+
+        let deployed = provider
+            .send_transaction(tx)
+            .await
+            .unwrap()
+            .get_receipt()
+            .await
+            .unwrap()
+            .contract_address
+            .unwrap();
+
+        let code = provider.get_code_at(deployed).await.unwrap();
+
+        provider
+            .anvil_set_code(MULTICALL3_ADDRESS, code)
+            .await
+            .unwrap();
+    }
 }
@@ -1,6 +1,6 @@
 mod common;
 
-use alloy::primitives::U256;
+use alloy::{primitives::U256, signers::local::PrivateKeySigner};
 use alloy_erc20::LazyTokenSigner;
 use common::{
     TestContext, ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, ANVIL_ADDRESS_2, ONE_TOKEN, TEN_TOKENS,
@@ -333,3 +333,23 @@ async fn test_transfer_does_not_affect_total_supply() {
     let supply_after_transfer = token.total_supply().await.unwrap();
     assert_eq!(initial_supply, supply_after_transfer);
 }
+
+// =============================================================================
+// Permit Tests
+// =============================================================================
+
+#[tokio::test]
+async fn test_sign_permit_unsupported_token() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let token = LazyTokenSigner::new(token_address, provider);
+    let signer = PrivateKeySigner::random();
+
+    let result = token
+        .sign_permit(&signer, ANVIL_ADDRESS_1, U256::from(ONE_TOKEN), U256::MAX)
+        .await;
+
+    assert!(result.is_err());
+}
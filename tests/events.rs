@@ -0,0 +1,146 @@
+mod common;
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use alloy_erc20::{BasicTokenStore, Erc20ProviderExt, LazyToken, LazyTokenSigner, TransferFilter};
+use common::{
+    TestContext, ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, ANVIL_ADDRESS_2, ONE_TOKEN, TEN_TOKENS,
+};
+use futures::{StreamExt, TryStreamExt};
+
+#[tokio::test]
+async fn test_transfer_events() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(TEN_TOKENS))
+        .await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    LazyTokenSigner::new(token_address, provider.clone())
+        .transfer(ANVIL_ADDRESS_1, U256::from(ONE_TOKEN))
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    let latest = provider.get_block_number().await.unwrap();
+    let mut store = BasicTokenStore::new();
+
+    let events = provider
+        .transfer_events(token_address, 0..=latest, &mut store)
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+
+    // Mint
+    assert_eq!(events[0].from, Address::ZERO);
+    assert_eq!(events[0].to, ANVIL_ADDRESS_0);
+    assert_eq!(events[0].amount().to_string(), "10.000000000000000000");
+
+    // Transfer
+    assert_eq!(events[1].from, ANVIL_ADDRESS_0);
+    assert_eq!(events[1].to, ANVIL_ADDRESS_1);
+    assert_eq!(events[1].value, U256::from(ONE_TOKEN));
+    assert_eq!(events[1].token.symbol, "TEST");
+    assert!(events[1].block_number.is_some());
+}
+
+#[tokio::test]
+async fn test_approval_events() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    LazyTokenSigner::new(token_address, provider.clone())
+        .approve(ANVIL_ADDRESS_1, U256::from(ONE_TOKEN))
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    let latest = provider.get_block_number().await.unwrap();
+    let mut store = BasicTokenStore::new();
+
+    let events = provider
+        .approval_events(token_address, 0..=latest, &mut store)
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].owner, ANVIL_ADDRESS_0);
+    assert_eq!(events[0].spender, ANVIL_ADDRESS_1);
+    assert_eq!(events[0].amount().to_string(), "1.000000000000000000");
+}
+
+#[tokio::test]
+async fn test_transfer_events_empty_range() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(ONE_TOKEN))
+        .await;
+    let provider = ctx.create_provider();
+    let mut store = BasicTokenStore::new();
+
+    #[allow(clippy::reversed_empty_ranges)]
+    let events = provider
+        .transfer_events(token_address, 1..=0, &mut store)
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert!(events.is_empty());
+}
+
+#[tokio::test]
+async fn test_watch_transfers() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(TEN_TOKENS))
+        .await;
+    let provider = ctx.create_provider_with_signer(0);
+    let token = LazyToken::new(token_address, ctx.create_provider());
+
+    let mut transfers = token
+        .watch_transfers(TransferFilter::new().to(ANVIL_ADDRESS_2))
+        .await
+        .unwrap();
+
+    let signer = LazyTokenSigner::new(token_address, provider);
+
+    // Filtered out
+    signer
+        .transfer(ANVIL_ADDRESS_1, U256::from(ONE_TOKEN))
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    signer
+        .transfer(ANVIL_ADDRESS_2, U256::from(ONE_TOKEN))
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    let event = transfers.next().await.unwrap().unwrap();
+
+    assert_eq!(event.from, ANVIL_ADDRESS_0);
+    assert_eq!(event.to, ANVIL_ADDRESS_2);
+    assert_eq!(event.value, U256::from(ONE_TOKEN));
+    assert_eq!(event.token.symbol, "TEST");
+}
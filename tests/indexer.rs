@@ -0,0 +1,49 @@
+mod common;
+
+use alloy::{primitives::U256, providers::Provider};
+use alloy_erc20::{BalanceIndexer, LazyToken, LazyTokenSigner};
+use common::{TestContext, ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, ONE_TOKEN, TEN_TOKENS};
+
+#[tokio::test]
+async fn test_replay_balances() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(TEN_TOKENS))
+        .await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let receipt = LazyTokenSigner::new(token_address, provider.clone())
+        .transfer(ANVIL_ADDRESS_1, U256::from(ONE_TOKEN))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    let transfer_block = receipt.block_number.unwrap();
+
+    let latest = provider.get_block_number().await.unwrap();
+    let indexer = BalanceIndexer::new(
+        LazyToken::new(token_address, ctx.create_provider()),
+        [ANVIL_ADDRESS_0, ANVIL_ADDRESS_1],
+    );
+
+    let history = indexer.replay(0..=latest).await.unwrap();
+
+    // Mint and transfer
+    assert_eq!(history.snapshots.len(), 2);
+    assert!(history.discrepancies.is_empty());
+    assert_eq!(
+        history.balance_at(ANVIL_ADDRESS_0, transfer_block - 1),
+        Some(U256::from(TEN_TOKENS))
+    );
+    assert_eq!(
+        history.balance_at(ANVIL_ADDRESS_0, transfer_block),
+        Some(U256::from(TEN_TOKENS - ONE_TOKEN))
+    );
+    assert_eq!(
+        history.balance_at(ANVIL_ADDRESS_1, latest),
+        Some(U256::from(ONE_TOKEN))
+    );
+
+    assert!(indexer.verify(&history).await.unwrap().is_empty());
+}
@@ -1,7 +1,10 @@
 mod common;
 
-use alloy::primitives::U256;
-use alloy_erc20::{LazyToken, LazyTokenSigner};
+use alloy::{primitives::U256, providers::Provider};
+use alloy_erc20::{
+    Erc20ProviderExt, InternalError, LazyToken, LazyTokenSigner, Permit2, StringEncoding, Token,
+    TokenAmount, NATIVE_ADDRESS, PERMIT2_ADDRESS,
+};
 use common::{
     TestContext, ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, ANVIL_ADDRESS_2, HUNDRED_TOKENS, ONE_TOKEN,
     TEN_TOKENS,
@@ -466,3 +469,243 @@ async fn test_lazy_token_signer_complex_workflow() {
     assert_eq!(balance_0 + balance_1 + balance_2, total_supply);
     assert_eq!(total_supply, initial_mint);
 }
+
+// =============================================================================
+// LazyTokenSigner Safe Operations Tests
+// =============================================================================
+
+#[tokio::test]
+async fn test_lazy_token_signer_safe_transfer() {
+    let ctx = TestContext::new().await;
+    let mint_amount = U256::from(ONE_TOKEN);
+    let token_address = ctx.deploy_and_mint(ANVIL_ADDRESS_0, mint_amount).await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let token = LazyTokenSigner::new(token_address, provider);
+
+    let receipt = token
+        .safe_transfer(ANVIL_ADDRESS_1, mint_amount)
+        .await
+        .unwrap();
+    assert!(receipt.status());
+
+    let recipient_balance = token.balance_of(ANVIL_ADDRESS_1).await.unwrap();
+    assert_eq!(recipient_balance, mint_amount);
+}
+
+#[tokio::test]
+async fn test_lazy_token_signer_safe_transfer_insufficient_balance() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let token = LazyTokenSigner::new(token_address, provider);
+
+    let result = token
+        .safe_transfer(ANVIL_ADDRESS_1, U256::from(ONE_TOKEN))
+        .await;
+
+    assert!(matches!(
+        result.unwrap_err().source,
+        InternalError::Contract(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_lazy_token_signer_safe_operations_without_code() {
+    let ctx = TestContext::new().await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    // An EOA returns empty data to any call
+    let token = LazyTokenSigner::new(ANVIL_ADDRESS_2, provider);
+
+    let err = token
+        .safe_transfer(ANVIL_ADDRESS_1, U256::from(ONE_TOKEN))
+        .await
+        .unwrap_err();
+    assert!(matches!(err.source, InternalError::NoCode));
+
+    let err = token
+        .safe_approve(ANVIL_ADDRESS_1, U256::from(ONE_TOKEN))
+        .await
+        .unwrap_err();
+    assert!(matches!(err.source, InternalError::NoCode));
+
+    let err = token
+        .safe_transfer_from(ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, U256::from(ONE_TOKEN))
+        .await
+        .unwrap_err();
+    assert!(matches!(err.source, InternalError::NoCode));
+}
+
+#[tokio::test]
+async fn test_lazy_token_signer_safe_approve_and_transfer_from() {
+    let ctx = TestContext::new().await;
+    let mint_amount = U256::from(TEN_TOKENS);
+    let token_address = ctx.deploy_and_mint(ANVIL_ADDRESS_0, mint_amount).await;
+
+    let token_0 = LazyTokenSigner::new(token_address, ctx.create_provider_with_signer(0));
+    let token_1 = LazyTokenSigner::new(token_address, ctx.create_provider_with_signer(1));
+
+    token_0
+        .safe_approve(ANVIL_ADDRESS_1, mint_amount)
+        .await
+        .unwrap();
+
+    token_1
+        .safe_transfer_from(ANVIL_ADDRESS_0, ANVIL_ADDRESS_2, mint_amount)
+        .await
+        .unwrap();
+
+    let balance = token_1.balance_of(ANVIL_ADDRESS_2).await.unwrap();
+    assert_eq!(balance, mint_amount);
+}
+
+// =============================================================================
+// LazyTokenSigner TokenAmount Tests
+// =============================================================================
+
+#[tokio::test]
+async fn test_lazy_token_signer_transfer_amount() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(TEN_TOKENS))
+        .await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let token = LazyTokenSigner::new(token_address, provider);
+    let amount = TokenAmount::new(
+        Token::new(token_address, "TEST".to_string(), 18),
+        U256::from(ONE_TOKEN),
+    );
+
+    token
+        .transfer_amount(ANVIL_ADDRESS_1, &amount)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    let balance = token.balance_of(ANVIL_ADDRESS_1).await.unwrap();
+    assert_eq!(balance, amount.raw());
+}
+
+#[tokio::test]
+async fn test_lazy_token_signer_approve_amount_token_mismatch() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_token().await;
+    let other_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let token = LazyTokenSigner::new(token_address, provider);
+    let amount = TokenAmount::new(
+        Token::new(other_address, "TEST".to_string(), 18),
+        U256::from(ONE_TOKEN),
+    );
+
+    let result = token.approve_amount(ANVIL_ADDRESS_1, &amount).await;
+
+    assert!(matches!(
+        result.unwrap_err().source,
+        InternalError::TokenMismatch(address) if address == other_address
+    ));
+}
+
+// =============================================================================
+// Permit2 Tests
+// =============================================================================
+
+#[tokio::test]
+async fn test_permit2_token_approval_detection() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let token = LazyTokenSigner::new(token_address, provider.clone());
+    let permit2 = Permit2::new(provider);
+
+    let approved = permit2
+        .is_approved(token_address, ANVIL_ADDRESS_0, U256::from(1))
+        .await
+        .unwrap();
+    assert!(!approved);
+
+    token
+        .approve(PERMIT2_ADDRESS, U256::MAX)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    let approved = permit2
+        .is_approved(token_address, ANVIL_ADDRESS_0, U256::from(HUNDRED_TOKENS))
+        .await
+        .unwrap();
+    assert!(approved);
+}
+
+// =============================================================================
+// Native Asset Tests
+// =============================================================================
+
+#[tokio::test]
+async fn test_native_asset_read_operations() {
+    let ctx = TestContext::new().await;
+    let provider = ctx.create_provider();
+
+    let token = LazyToken::new(NATIVE_ADDRESS, provider.clone());
+
+    assert!(token.is_native());
+    assert_eq!(token.symbol().await.unwrap(), "ETH");
+    assert_eq!(*token.decimals().await.unwrap(), 18);
+    assert_eq!(
+        token.balance_of(ANVIL_ADDRESS_0).await.unwrap(),
+        provider.get_balance(ANVIL_ADDRESS_0).await.unwrap()
+    );
+    assert_eq!(
+        token.symbol_decoded().await.unwrap().encoding,
+        StringEncoding::Native
+    );
+    assert!(matches!(
+        token.total_supply().await.unwrap_err().source,
+        InternalError::NativeAsset("totalSupply")
+    ));
+
+    let retrieved = provider
+        .retrieve_token_decoded(NATIVE_ADDRESS)
+        .await
+        .unwrap();
+    assert!(retrieved.token.is_native());
+    assert_eq!(retrieved.token.symbol, "ETH");
+    assert_eq!(retrieved.symbol_encoding, StringEncoding::Native);
+    assert_eq!(retrieved.name_encoding, Some(StringEncoding::Native));
+}
+
+#[tokio::test]
+async fn test_native_asset_transfer() {
+    let ctx = TestContext::new().await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let token = LazyTokenSigner::new(NATIVE_ADDRESS, provider);
+    let initial_balance = token.balance_of(ANVIL_ADDRESS_1).await.unwrap();
+
+    token
+        .safe_transfer(ANVIL_ADDRESS_1, U256::from(ONE_TOKEN))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token.balance_of(ANVIL_ADDRESS_1).await.unwrap(),
+        initial_balance + U256::from(ONE_TOKEN)
+    );
+    assert!(matches!(
+        token
+            .approve(ANVIL_ADDRESS_1, U256::from(1))
+            .await
+            .unwrap_err()
+            .source,
+        InternalError::NativeAsset("approve")
+    ));
+}
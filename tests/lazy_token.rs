@@ -1,7 +1,9 @@
 mod common;
 
-use alloy::primitives::U256;
-use alloy_erc20::LazyToken;
+use alloy::{eips::BlockId, primitives::U256, providers::Provider};
+use alloy_erc20::{
+    DecimalsStatus, LazyToken, StringEncoding, TokenProfileCache, ERC165_INTERFACE_ID,
+};
 use common::{TestContext, ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, ONE_TOKEN};
 
 #[tokio::test]
@@ -28,6 +30,19 @@ async fn test_lazy_token_symbol() {
     assert_eq!(symbol, "TEST");
 }
 
+#[tokio::test]
+async fn test_lazy_token_symbol_decoded() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+
+    let token = LazyToken::new(token_address, provider);
+    let symbol = token.symbol_decoded().await.unwrap();
+
+    assert_eq!(symbol.value, "TEST");
+    assert_eq!(symbol.encoding, StringEncoding::String);
+}
+
 #[tokio::test]
 async fn test_lazy_token_decimals() {
     let ctx = TestContext::new().await;
@@ -90,6 +105,38 @@ async fn test_lazy_token_balance_of_after_mint() {
     assert_eq!(balance, mint_amount);
 }
 
+#[tokio::test]
+async fn test_lazy_token_balance_of_at() {
+    let ctx = TestContext::new().await;
+    let mint_amount = U256::from(ONE_TOKEN);
+    let token_address = ctx.deploy_and_mint(ANVIL_ADDRESS_0, mint_amount).await;
+    let provider = ctx.create_provider();
+    let minted_at = provider.get_block_number().await.unwrap();
+
+    ctx.mint_tokens(token_address, ANVIL_ADDRESS_0, mint_amount)
+        .await;
+
+    let token = LazyToken::new(token_address, provider);
+    let before = token
+        .balance_of_at(ANVIL_ADDRESS_0, BlockId::number(minted_at - 1))
+        .await
+        .unwrap();
+    let at = token
+        .balance_of_at(ANVIL_ADDRESS_0, BlockId::number(minted_at))
+        .await
+        .unwrap();
+    let total_supply = token
+        .total_supply_at(BlockId::number(minted_at))
+        .await
+        .unwrap();
+    let latest = token.balance_of(ANVIL_ADDRESS_0).await.unwrap();
+
+    assert_eq!(before, U256::ZERO);
+    assert_eq!(at, mint_amount);
+    assert_eq!(total_supply, mint_amount);
+    assert_eq!(latest, mint_amount * U256::from(2));
+}
+
 #[tokio::test]
 async fn test_lazy_token_get_balance_conversion() {
     let ctx = TestContext::new().await;
@@ -142,3 +189,48 @@ async fn test_lazy_token_caches_metadata() {
     assert_eq!(decimals1, decimals2);
     assert_eq!(*decimals1, 18);
 }
+
+#[tokio::test]
+async fn test_lazy_token_supports_interface() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+
+    let token = LazyToken::new(token_address, provider);
+
+    // A plain ERC-20 doesn't implement ERC-165
+    assert!(!token.supports_interface(ERC165_INTERFACE_ID).await.unwrap());
+    assert!(!token.supports_erc1363().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_lazy_token_profile() {
+    let ctx = TestContext::new().await;
+    ctx.deploy_multicall3().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+
+    let token = LazyToken::new(token_address, provider);
+    let profile = token.profile().await.unwrap();
+
+    assert!(profile.has_code);
+    assert_eq!(profile.proxy, None);
+    assert_eq!(profile.decimals, DecimalsStatus::Valid(18));
+    assert!(!profile.erc165);
+    assert!(!profile.permit);
+    assert!(!profile.erc3009);
+    assert!(!profile.erc1363);
+    assert!(!profile.erc4626);
+    assert_eq!(profile.approve_requires_reset, Some(false));
+
+    let mut cache = TokenProfileCache::new();
+    assert_eq!(cache.get_or_probe(&token).await.unwrap(), &profile);
+    assert!(cache.contains(31337, token_address));
+
+    // An account without code
+    let account = LazyToken::new(ANVIL_ADDRESS_1, ctx.create_provider());
+    let profile = account.profile().await.unwrap();
+
+    assert!(!profile.has_code);
+    assert_eq!(profile.decimals, DecimalsStatus::Reverts);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use alloy::primitives::U256;
+use alloy_erc20::{InternalError, LazyTokenSigner};
+use common::{
+    derive_anvil_signer, TestContext, ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, ANVIL_ADDRESS_2, ONE_TOKEN,
+    TEN_TOKENS,
+};
+
+#[tokio::test]
+async fn test_permit() {
+    let ctx = TestContext::new().await;
+    // Version "2" is only found after trying "1"
+    let token_address = ctx.deploy_fiat_token("2").await;
+    ctx.mint_tokens(token_address, ANVIL_ADDRESS_0, U256::from(TEN_TOKENS))
+        .await;
+
+    let owner = derive_anvil_signer(0);
+    assert_eq!(owner.address(), ANVIL_ADDRESS_0);
+
+    // The permit is relayed by another account
+    let relayer = LazyTokenSigner::new(token_address, ctx.create_provider_with_signer(2));
+    let domain = relayer.eip712_domain().await.unwrap();
+    assert_eq!(domain.name.as_deref(), Some("Fiat Token"));
+    assert_eq!(domain.version.as_deref(), Some("2"));
+    assert_eq!(
+        domain.separator(),
+        relayer.domain_separator().await.unwrap()
+    );
+
+    assert_eq!(relayer.nonces(ANVIL_ADDRESS_0).await.unwrap(), U256::ZERO);
+    let signed = relayer
+        .sign_permit(&owner, ANVIL_ADDRESS_1, U256::from(ONE_TOKEN), U256::MAX)
+        .await
+        .unwrap();
+    assert_eq!(signed.permit.owner, ANVIL_ADDRESS_0);
+    assert_eq!(signed.permit.nonce, U256::ZERO);
+
+    relayer
+        .permit(&signed)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        relayer
+            .allowance(ANVIL_ADDRESS_0, ANVIL_ADDRESS_1)
+            .await
+            .unwrap(),
+        U256::from(ONE_TOKEN)
+    );
+    assert_eq!(
+        relayer.nonces(ANVIL_ADDRESS_0).await.unwrap(),
+        U256::from(1)
+    );
+
+    // The nonce is used up, so the permit can't be replayed
+    assert!(relayer.permit(&signed).await.is_err());
+
+    // The spender can use the allowance
+    let spender = LazyTokenSigner::new(token_address, ctx.create_provider_with_signer(1));
+    spender
+        .transfer_from(ANVIL_ADDRESS_0, ANVIL_ADDRESS_2, U256::from(ONE_TOKEN))
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+    assert_eq!(
+        spender.balance_of(ANVIL_ADDRESS_2).await.unwrap(),
+        U256::from(ONE_TOKEN)
+    );
+}
+
+#[tokio::test]
+async fn test_permit_unknown_domain() {
+    let ctx = TestContext::new().await;
+    // Neither "1" nor "2", and no ERC-5267 to read it from
+    let token_address = ctx.deploy_fiat_token("3").await;
+    let token = LazyTokenSigner::new(token_address, ctx.create_provider_with_signer(0));
+
+    let separator = token.domain_separator().await.unwrap();
+    let err = token.eip712_domain().await.unwrap_err();
+    assert_eq!(err.token, token_address.into());
+    assert!(matches!(err.source, InternalError::UnknownDomain(s) if s == separator));
+
+    let err = token
+        .sign_permit(
+            &derive_anvil_signer(0),
+            ANVIL_ADDRESS_1,
+            U256::from(ONE_TOKEN),
+            U256::MAX,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err.source, InternalError::UnknownDomain(_)));
+}
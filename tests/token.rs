@@ -1,8 +1,13 @@
 mod common;
 
-use alloy::primitives::U256;
-use alloy_erc20::{Erc20ProviderExt, Token};
-use common::{TestContext, ANVIL_ADDRESS_0, ONE_TOKEN};
+use alloy::{eips::BlockId, primitives::U256, providers::Provider};
+use alloy_erc20::{
+    BasicTokenStore, ConcurrentTokenStore, ConflictPolicy, Erc20ProviderExt, InternalError,
+    StringEncoding, Token, TokenId, TokenStore, NATIVE_ADDRESS,
+};
+use common::{TestContext, ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, ONE_TOKEN};
+use futures::future::join_all;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_retrieve_token_symbol() {
@@ -37,6 +42,69 @@ async fn test_retrieve_token_address() {
     assert_eq!(token.address, token_address);
 }
 
+#[tokio::test]
+async fn test_retrieve_tokens() {
+    let ctx = TestContext::new().await;
+    ctx.deploy_multicall3().await;
+    let first = ctx.deploy_token().await;
+    let second = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+
+    let tokens = provider.retrieve_tokens(&[first, second]).await.unwrap();
+
+    assert_eq!(tokens.len(), 2);
+    for (token, address) in tokens.into_iter().zip([first, second]) {
+        let token = token.unwrap();
+        assert_eq!(token.address, address);
+        assert_eq!(token.symbol, "TEST");
+        assert_eq!(token.decimals, 18);
+        assert_eq!(token.name.as_deref(), Some("Test Token"));
+    }
+}
+
+#[tokio::test]
+async fn test_retrieve_tokens_decoded() {
+    let ctx = TestContext::new().await;
+    ctx.deploy_multicall3().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+
+    let token = provider
+        .retrieve_token_decoded(token_address)
+        .await
+        .unwrap();
+
+    assert_eq!(token.token.symbol, "TEST");
+    assert_eq!(token.symbol_encoding, StringEncoding::String);
+    assert_eq!(token.name_encoding, None);
+
+    let tokens = provider
+        .retrieve_tokens_decoded(&[token_address])
+        .await
+        .unwrap();
+    let token = tokens[0].as_ref().unwrap();
+
+    assert_eq!(token.symbol_encoding, StringEncoding::String);
+    assert_eq!(token.name_encoding, Some(StringEncoding::String));
+}
+
+#[tokio::test]
+async fn test_retrieve_tokens_partial_failure() {
+    let ctx = TestContext::new().await;
+    ctx.deploy_multicall3().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+
+    // An EOA doesn't implement ERC-20, but must not fail the whole batch
+    let tokens = provider
+        .retrieve_tokens(&[ANVIL_ADDRESS_1, token_address])
+        .await
+        .unwrap();
+
+    assert!(tokens[0].is_err());
+    assert_eq!(tokens[1].as_ref().unwrap().symbol, "TEST");
+}
+
 #[tokio::test]
 async fn test_token_get_balance_conversion() {
     let ctx = TestContext::new().await;
@@ -118,6 +186,43 @@ async fn test_provider_balance_of_after_mint() {
     assert_eq!(balance.to_string(), "1.000000000000000000");
 }
 
+#[tokio::test]
+async fn test_provider_balance_of_at() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+    let deployed_at = provider.get_block_number().await.unwrap();
+
+    ctx.mint_tokens(token_address, ANVIL_ADDRESS_0, U256::from(ONE_TOKEN))
+        .await;
+
+    let before = provider
+        .balance_of_at(token_address, ANVIL_ADDRESS_0, BlockId::number(deployed_at))
+        .await
+        .unwrap();
+    let after = provider
+        .balance_of(token_address, ANVIL_ADDRESS_0)
+        .await
+        .unwrap();
+
+    assert_eq!(before.to_string(), "0");
+    assert_eq!(after.to_string(), "1.000000000000000000");
+}
+
+#[tokio::test]
+async fn test_provider_balance_of_error_holder() {
+    let ctx = TestContext::new().await;
+    let provider = ctx.create_provider();
+
+    // An EOA doesn't implement `balanceOf`
+    let err = provider
+        .balance_of(ANVIL_ADDRESS_1, ANVIL_ADDRESS_0)
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.token, TokenId::Address(ANVIL_ADDRESS_0));
+}
+
 #[tokio::test]
 async fn test_provider_balance_of_multiple_tokens() {
     let ctx = TestContext::new().await;
@@ -132,3 +237,226 @@ async fn test_provider_balance_of_multiple_tokens() {
 
     assert_eq!(balance.to_string(), "10.000000000000000000");
 }
+
+#[tokio::test]
+async fn test_provider_balances_of() {
+    let ctx = TestContext::new().await;
+    ctx.deploy_multicall3().await;
+    let first = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(ONE_TOKEN))
+        .await;
+    let second = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_1, U256::from(common::TEN_TOKENS))
+        .await;
+    let provider = ctx.create_provider();
+    let mut store = BasicTokenStore::new();
+
+    let balances = provider
+        .balances_of(
+            &[
+                (first, ANVIL_ADDRESS_0),
+                (first, ANVIL_ADDRESS_1),
+                (second, ANVIL_ADDRESS_1),
+            ],
+            &mut store,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balances[&(first, ANVIL_ADDRESS_0)]
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "1.000000000000000000"
+    );
+    assert_eq!(
+        balances[&(first, ANVIL_ADDRESS_1)]
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "0"
+    );
+    assert_eq!(
+        balances[&(second, ANVIL_ADDRESS_1)]
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "10.000000000000000000"
+    );
+
+    // Missing tokens were fetched in the same round-trip and cached
+    assert!(store.contains(31337, first.into()));
+    assert!(store.contains(31337, second.into()));
+}
+
+#[tokio::test]
+async fn test_provider_balances_of_native() {
+    let ctx = TestContext::new().await;
+    ctx.deploy_multicall3().await;
+    let provider = ctx.create_provider();
+    let mut store = BasicTokenStore::new();
+
+    let balances = provider
+        .balances_of(&[(NATIVE_ADDRESS, ANVIL_ADDRESS_0)], &mut store)
+        .await
+        .unwrap();
+
+    assert!(balances[&(NATIVE_ADDRESS, ANVIL_ADDRESS_0)].is_ok());
+    assert!(!store.contains(31337, NATIVE_ADDRESS.into()));
+}
+
+#[tokio::test]
+async fn test_provider_balances_of_uses_store_decimals() {
+    let ctx = TestContext::new().await;
+    ctx.deploy_multicall3().await;
+    let token_address = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(ONE_TOKEN))
+        .await;
+    let provider = ctx.create_provider();
+    let mut store = BasicTokenStore::new();
+
+    // Pretend the token has 6 decimals to check the store is used
+    store.insert(31337, Token::new(token_address, "TEST".to_string(), 6));
+
+    let balances = provider
+        .balances_of(&[(token_address, ANVIL_ADDRESS_0)], &mut store)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balances[&(token_address, ANVIL_ADDRESS_0)]
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "1000000000000.000000"
+    );
+}
+
+#[cfg(feature = "lru-store")]
+#[tokio::test]
+async fn test_provider_balances_of_with_evictions() {
+    use alloy_erc20::LruTokenStore;
+    use std::num::NonZeroUsize;
+
+    let ctx = TestContext::new().await;
+    ctx.deploy_multicall3().await;
+    let first = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(ONE_TOKEN))
+        .await;
+    let second = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+
+    // Room for a single token, keyed by address and symbol
+    let mut store = LruTokenStore::new(NonZeroUsize::new(2).unwrap());
+    provider.get_token(first, &mut store).await.unwrap();
+
+    let balances = provider
+        .balances_of(
+            &[(first, ANVIL_ADDRESS_0), (second, ANVIL_ADDRESS_0)],
+            &mut store,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balances[&(first, ANVIL_ADDRESS_0)]
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "1.000000000000000000"
+    );
+    assert!(balances[&(second, ANVIL_ADDRESS_0)].is_ok());
+}
+
+#[tokio::test]
+async fn test_provider_get_token_symbol_conflict() {
+    let ctx = TestContext::new().await;
+    let first = ctx.deploy_token().await;
+    let second = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+    let mut store = BasicTokenStore::with_conflict_policy(ConflictPolicy::Reject);
+
+    provider.get_token(first, &mut store).await.unwrap();
+
+    // Both tokens are "TEST"
+    let err = provider.get_token(second, &mut store).await.unwrap_err();
+    assert!(matches!(err.source, InternalError::SymbolConflict(address) if address == first));
+    assert!(!store.contains(31337, second.into()));
+}
+
+#[tokio::test]
+async fn test_provider_get_token_shared() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+    let store = ConcurrentTokenStore::new();
+
+    let tokens = join_all((0..5).map(|_| provider.get_token_shared(token_address, &store))).await;
+    let first = tokens[0].as_ref().unwrap();
+
+    assert_eq!(first.symbol, "TEST");
+    assert!(tokens
+        .iter()
+        .all(|token| Arc::ptr_eq(token.as_ref().unwrap(), first)));
+
+    let by_symbol = provider
+        .get_token_shared(TokenId::Symbol("TEST".to_string()), &store)
+        .await
+        .unwrap();
+    assert_eq!(by_symbol.address, token_address);
+}
+
+#[tokio::test]
+async fn test_provider_get_token_with_resolver() {
+    let ctx = TestContext::new().await;
+    let first = ctx.deploy_token().await;
+    let second = ctx.deploy_token().await;
+    let provider = ctx.create_provider();
+    let mut store = BasicTokenStore::new();
+
+    let resolver = move |_: u64, symbol: &str| match symbol {
+        "TEST" => vec![first],
+        "DUP" => vec![first, second],
+        _ => Vec::new(),
+    };
+
+    let token = provider
+        .get_token_with_resolver(TokenId::Symbol("TEST".to_string()), &mut store, &resolver)
+        .await
+        .unwrap();
+    assert_eq!(token.address, first);
+    assert!(store.contains(31337, first.into()));
+
+    let err = provider
+        .get_token_with_resolver(TokenId::Symbol("DUP".to_string()), &mut store, &resolver)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err.source, InternalError::AmbiguousSymbol(ref addresses) if addresses.len() == 2)
+    );
+
+    let err = provider
+        .get_token_with_resolver(TokenId::Symbol("NONE".to_string()), &mut store, &resolver)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.source, InternalError::NotInStore(_)));
+
+    // Both tokens are "TEST", so the symbol is ambiguous once both are stored
+    store.insert(31337, Token::new(second, "TEST".to_string(), 18));
+    let err = provider
+        .get_token_with_resolver(TokenId::Symbol("TEST".to_string()), &mut store, &resolver)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err.source, InternalError::AmbiguousSymbol(ref addresses) if addresses.len() == 2)
+    );
+
+    let mut store = BasicTokenStore::with_conflict_policy(ConflictPolicy::Reject);
+    store.insert(31337, Token::new(second, "TEST".to_string(), 18));
+    let err = provider
+        .get_token_with_resolver(first, &mut store, &resolver)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.source, InternalError::SymbolConflict(address) if address == second));
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use alloy::primitives::U256;
+use alloy_erc20::{LazyTokenSigner, LazyVault, LazyVaultSigner};
+use bigdecimal::BigDecimal;
+use common::{
+    TestContext, ANVIL_ADDRESS_0, ANVIL_ADDRESS_1, ANVIL_ADDRESS_2, HUNDRED_TOKENS, ONE_TOKEN,
+    TEN_TOKENS,
+};
+
+#[tokio::test]
+async fn test_vault_shares_token() {
+    let ctx = TestContext::new().await;
+    let token_address = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(ONE_TOKEN))
+        .await;
+    let provider = ctx.create_provider();
+
+    let vault = LazyVault::new(token_address, provider);
+
+    assert_eq!(vault.token().symbol().await.unwrap(), "TEST");
+    assert_eq!(
+        vault.token().balance_of(ANVIL_ADDRESS_0).await.unwrap(),
+        U256::from(ONE_TOKEN)
+    );
+
+    // A plain ERC-20 isn't a vault
+    assert!(vault.asset().await.is_err());
+    assert!(vault.total_assets().await.is_err());
+    assert!(vault.balance_value(ANVIL_ADDRESS_0).await.is_err());
+}
+
+#[tokio::test]
+async fn test_vault_operations() {
+    let ctx = TestContext::new().await;
+    let asset_address = ctx
+        .deploy_and_mint(ANVIL_ADDRESS_0, U256::from(HUNDRED_TOKENS))
+        .await;
+    let vault_address = ctx.deploy_vault(asset_address).await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let asset = LazyTokenSigner::new(asset_address, provider.clone());
+    let signer = LazyVaultSigner::new(vault_address, provider);
+    let vault = signer.vault();
+    let one = U256::from(ONE_TOKEN);
+    let two = U256::from(2 * ONE_TOKEN);
+    let ten = U256::from(TEN_TOKENS);
+
+    asset
+        .approve(vault_address, U256::MAX)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    assert_eq!(*vault.asset().await.unwrap(), asset_address);
+    assert_eq!(vault.asset_token().await.unwrap().symbol, "TEST");
+    assert_eq!(vault.max_deposit(ANVIL_ADDRESS_0).await.unwrap(), U256::MAX);
+    assert_eq!(vault.max_mint(ANVIL_ADDRESS_0).await.unwrap(), U256::MAX);
+
+    // The first deposit mints shares 1:1
+    assert_eq!(vault.preview_deposit(ten).await.unwrap(), ten);
+    signer
+        .deposit(ten, ANVIL_ADDRESS_0)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+    assert_eq!(
+        vault.token().balance_of(ANVIL_ADDRESS_0).await.unwrap(),
+        ten
+    );
+    assert_eq!(vault.total_assets().await.unwrap(), ten);
+
+    // Assets sent to the vault double the shares value
+    asset
+        .transfer(vault_address, ten)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+    assert_eq!(vault.convert_to_assets(one).await.unwrap(), two);
+    assert_eq!(vault.convert_to_shares(two).await.unwrap(), one);
+    assert_eq!(vault.preview_mint(one).await.unwrap(), two);
+    assert_eq!(vault.preview_withdraw(two).await.unwrap(), one);
+    assert_eq!(vault.preview_redeem(one).await.unwrap(), two);
+    assert_eq!(
+        vault.max_withdraw(ANVIL_ADDRESS_0).await.unwrap(),
+        U256::from(2 * TEN_TOKENS)
+    );
+    assert_eq!(vault.max_redeem(ANVIL_ADDRESS_0).await.unwrap(), ten);
+    assert_eq!(vault.share_value(one).await.unwrap(), BigDecimal::from(2));
+    assert_eq!(
+        vault.balance_value(ANVIL_ADDRESS_0).await.unwrap(),
+        BigDecimal::from(20)
+    );
+
+    signer
+        .mint(one, ANVIL_ADDRESS_1)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+    assert_eq!(
+        vault.token().balance_of(ANVIL_ADDRESS_1).await.unwrap(),
+        one
+    );
+    assert_eq!(
+        asset.balance_of(ANVIL_ADDRESS_0).await.unwrap(),
+        U256::from(78 * ONE_TOKEN)
+    );
+
+    signer
+        .withdraw(two, ANVIL_ADDRESS_2, ANVIL_ADDRESS_0)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+    assert_eq!(
+        vault.token().balance_of(ANVIL_ADDRESS_0).await.unwrap(),
+        U256::from(9 * ONE_TOKEN)
+    );
+    assert_eq!(asset.balance_of(ANVIL_ADDRESS_2).await.unwrap(), two);
+
+    signer
+        .redeem(one, ANVIL_ADDRESS_0, ANVIL_ADDRESS_0)
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+    assert_eq!(
+        vault.token().balance_of(ANVIL_ADDRESS_0).await.unwrap(),
+        U256::from(8 * ONE_TOKEN)
+    );
+    assert_eq!(
+        asset.balance_of(ANVIL_ADDRESS_0).await.unwrap(),
+        U256::from(80 * ONE_TOKEN)
+    );
+
+    // Redeeming more shares than owned fails, with the vault as token
+    let err = signer
+        .redeem(U256::from(HUNDRED_TOKENS), ANVIL_ADDRESS_0, ANVIL_ADDRESS_0)
+        .await
+        .unwrap_err();
+    assert_eq!(err.token, vault_address.into());
+}
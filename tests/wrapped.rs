@@ -0,0 +1,64 @@
+mod common;
+
+use alloy::{primitives::U256, providers::Provider};
+use alloy_erc20::WrappedNative;
+use common::{TestContext, ANVIL_ADDRESS_0, ONE_TOKEN, TEN_TOKENS};
+
+#[tokio::test]
+async fn test_wrapped_native_deposit_withdraw() {
+    let ctx = TestContext::new().await;
+    let weth_address = ctx.deploy_weth9().await;
+    let provider = ctx.create_provider_with_signer(0);
+
+    let weth = WrappedNative::new(weth_address, provider.clone());
+    assert_eq!(weth.token().symbol().await.unwrap(), "WETH");
+    assert_eq!(*weth.token().decimals().await.unwrap(), 18);
+
+    let ether_before = provider.get_balance(ANVIL_ADDRESS_0).await.unwrap();
+
+    weth.deposit(U256::from(TEN_TOKENS))
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        weth.token().balance_of(ANVIL_ADDRESS_0).await.unwrap(),
+        U256::from(TEN_TOKENS)
+    );
+    assert_eq!(
+        weth.token().total_supply().await.unwrap(),
+        U256::from(TEN_TOKENS)
+    );
+    // The deposit and its gas
+    assert!(
+        provider.get_balance(ANVIL_ADDRESS_0).await.unwrap()
+            < ether_before - U256::from(TEN_TOKENS)
+    );
+
+    let ether_before = provider.get_balance(ANVIL_ADDRESS_0).await.unwrap();
+
+    weth.withdraw(U256::from(ONE_TOKEN))
+        .await
+        .unwrap()
+        .watch()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        weth.token().balance_of(ANVIL_ADDRESS_0).await.unwrap(),
+        U256::from(TEN_TOKENS - ONE_TOKEN)
+    );
+    assert_eq!(
+        provider.get_balance(weth_address).await.unwrap(),
+        U256::from(TEN_TOKENS - ONE_TOKEN)
+    );
+    // The withdrawal minus its gas
+    let ether_after = provider.get_balance(ANVIL_ADDRESS_0).await.unwrap();
+    assert!(ether_after > ether_before);
+    assert!(ether_after < ether_before + U256::from(ONE_TOKEN));
+
+    // Withdrawing more than deposited fails
+    assert!(weth.withdraw(U256::from(TEN_TOKENS)).await.is_err());
+}